@@ -1,13 +1,19 @@
+#[cfg(all(windows, feature = "com"))]
 use objets_metier_rs::wrappers::CptaApplication;
+#[cfg(all(windows, feature = "com"))]
+use objets_metier_rs::com::ComApartment;
+#[cfg(all(windows, feature = "com"))]
 use objets_metier_rs::errors::SageResult;
 
-const BSCPTA_CLSID: &str = "309DE0FB-9FB8-4F4E-8295-CC60C60DAA33";
-
-#[allow(dead_code)] // A supprimer à la finalisation de la v0.2.0
-const BSCIAL_CLSID: &str = "ED0EC116-16B8-44CC-A68A-41BF6E15EB3F";
+#[cfg(not(all(windows, feature = "com")))]
+fn main() {
+    eprintln!("❌ Cet exemple nécessite Windows et la feature `com` (Objets Métier Sage 100c)");
+}
 
+#[cfg(all(windows, feature = "com"))]
 fn main() -> SageResult<()> {
     println!("🚀 Sage 100c - Interface Rust Élégante v0.1.3");
+    let _com = ComApartment::sta()?;
     
     // APPROCHE 1: Syntaxe Rust élégante similaire à C#/VB
     elegant_rust_approach()?;
@@ -22,11 +28,12 @@ fn main() -> SageResult<()> {
 /// NOUVELLE APPROCHE : Syntaxe Rust élégante
 /// Équivalent C#/VB : MaDLL.Loggable.UserName
 /// Syntaxe Rust     : app.loggable()?.user_name()?
+#[cfg(all(windows, feature = "com"))]
 fn elegant_rust_approach() -> SageResult<()> {
     println!("\n✨ === NOUVELLE SYNTAXE RUST ÉLÉGANTE ===");
     
     // Création de l'application - Équivalent : var app = new BSCPTAApplication()
-    let app = CptaApplication::new(BSCPTA_CLSID)?;
+    let app = CptaApplication::newest_installed()?;
     println!("✅ Application Sage créée");
     
     // Accès direct aux propriétés de base - Équivalent : app.Name, app.IsOpen
@@ -78,8 +85,9 @@ fn elegant_rust_approach() -> SageResult<()> {
 
 /// Fonction helper pour montrer les différentes syntaxes possibles
 #[allow(dead_code)]
+#[cfg(all(windows, feature = "com"))]
 fn syntax_examples() -> SageResult<()> {
-    let app = CptaApplication::new(BSCPTA_CLSID)?;
+    let app = CptaApplication::newest_installed()?;
     
     // === EXEMPLES DE SYNTAXES POSSIBLES ===
     
@@ -115,8 +123,9 @@ fn syntax_examples() -> SageResult<()> {
 
 /// Démonstrateur pour d'autres objets Factory
 #[allow(dead_code)]
+#[cfg(all(windows, feature = "com"))]
 fn factory_objects_example() -> SageResult<()> {
-    let app = CptaApplication::new(BSCPTA_CLSID)?;
+    let app = CptaApplication::newest_installed()?;
     
     // Dans le futur, on pourrait avoir :
     // let clients = app.factory_client()?.list()?;
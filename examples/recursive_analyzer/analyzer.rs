@@ -1,10 +1,11 @@
-#![recursion_limit = "256"]
-
 use std::collections::HashSet;
 use std::fs::OpenOptions;
 use std::io::Write;
 use std::sync::Mutex;
-use objets_metier_rs::com::{ComInstance, MemberType, SafeDispatch};
+use objets_metier_rs::com::{
+    ComApartment, ComInstance, MemberInfo, MemberType, SafeDispatch, TypeKind, TypeLibrary, WindowsRegistry,
+    discover_classes,
+};
 use objets_metier_rs::wrappers::CptaApplication;
 use objets_metier_rs::errors::{self, SageResult};
 use objets_metier_rs::CialApplication;
@@ -48,41 +49,6 @@ macro_rules! log_println {
     };
 }
 
-const BSCPTA_CLSID: &str = "309DE0FB-9FB8-4F4E-8295-CC60C60DAA33";
-const BSCIAL_CLSID: &str = "ED0EC116-16B8-44CC-A68A-41BF6E15EB3F";
-
-// Type libraries COM Sage connus
-const SAGE_TYPELIB_GUIDS: &[&str] = &[
-    "{00020430-0000-0000-C000-000000000046}", // Standard OLE
-    "{309DE0FB-9FB8-4F4E-8295-CC60C60DAA33}", // BSCPTAApplication100c
-    "{ED0EC116-16B8-44CC-A68A-41BF6E15EB3F}", // BSCIALApplication100c
-];
-
-// ProgIDs COM Sage connus à tester
-const SAGE_PROGIDS: &[&str] = &[
-    "BSCPTAApplication100c",
-    "BSCIALApplication100c",
-];
-
-/// Structure pour stocker les informations d'un TypeLib découvert
-#[derive(Debug, Clone)]
-struct TypeLibInfo {
-    name: String,
-    guid: String,
-    version: String,
-    interfaces: Vec<InterfaceInfo>,
-}
-
-/// Structure pour stocker les informations d'une interface COM
-#[derive(Debug, Clone)]
-struct InterfaceInfo {
-    name: String,
-    guid: String,
-    is_dual: bool,
-    methods: Vec<MethodInfo>,
-    properties: Vec<PropertyInfo>,
-}
-
 /// Structure pour stocker les informations d'un objet COM analysé
 #[derive(Debug, Clone)]
 struct ComObjectInfo {
@@ -119,12 +85,13 @@ struct ParameterInfo {
     is_optional: bool,
 }
 
-fn main() -> SageResult<()> {
+pub fn main() -> SageResult<()> {
     log_println!("🔍 Analyseur récursif des objets COM Sage 100c v0.1.3 - AVEC LOGGING");
     log_println!("═══════════════════════════════════════════════════════════════════");
     log_println!("🎯 Objectif: Cartographier TOUTE l'API Sage disponible");
     log_println!("📁 Toutes les sorties seront enregistrées dans: sage_analyzer_log.txt");
     log_println!();
+    let _com = ComApartment::sta()?;
     
     // PHASE 1: Scanner les Type Libraries pour découvrir tous les objets COM
     log_println!("🔎 === PHASE 1: DÉCOUVERTE DES TYPE LIBRARIES ===");
@@ -138,9 +105,9 @@ fn main() -> SageResult<()> {
     
     // PHASE 3: Analyse récursive de l'application principale
     log_println!("\n🚀 === PHASE 3: ANALYSE RÉCURSIVE TRADITIONNELLE ===");
-    let app_cpta = CptaApplication::new(BSCPTA_CLSID)?;
+    let app_cpta = CptaApplication::newest_installed()?;
     log_println!("✅ Application Comptabilité Sage créée");
-    let app_cial = CialApplication::new(BSCIAL_CLSID)?;
+    let app_cial = CialApplication::newest_installed()?;
     log_println!("✅ Application Gestion Commerciale Sage créée");
 
     let app_cpta_instance = app_cpta.instance();
@@ -228,22 +195,22 @@ fn main() -> SageResult<()> {
     Ok(())
 }
 
-/// Scanne les Type Libraries pour découvrir les interfaces COM Sage
-fn scan_sage_typelibs() -> SageResult<Vec<TypeLibInfo>> {
-    let mut discovered_typelibs = Vec::new();
+/// Charge les bibliothèques de types des classes Sage enregistrées
+fn scan_sage_typelibs() -> SageResult<Vec<TypeLibrary>> {
+    let mut discovered_typelibs: Vec<TypeLibrary> = Vec::new();
     
     log_println!("🔍 Scanning des Type Libraries Sage...");
     
-    for &guid_str in SAGE_TYPELIB_GUIDS {
-        log_println!("  🧪 Test du GUID: {}", guid_str);
+    for class in discover_classes(&WindowsRegistry)? {
+        log_println!("  🧪 {} (TypeLib {})", class.progid, class.typelib.as_deref().unwrap_or("?"));
         
-        match scan_single_typelib(guid_str) {
-            Ok(Some(typelib_info)) => {
-                log_println!("  ✅ TypeLib trouvée: {} (v{})", typelib_info.name, typelib_info.version);
-                discovered_typelibs.push(typelib_info);
+        match TypeLibrary::for_class(&class) {
+            Ok(typelib) if discovered_typelibs.iter().any(|t| t.guid.eq_ignore_ascii_case(&typelib.guid)) => {
+                log_println!("  ↩️  TypeLib {} déjà analysée", typelib.name);
             }
-            Ok(None) => {
-                log_println!("  ⚠️  TypeLib vide ou non accessible");
+            Ok(typelib) => {
+                log_println!("  ✅ TypeLib trouvée: {} (v{})", typelib.name, typelib.version);
+                discovered_typelibs.push(typelib);
             }
             Err(e) => {
                 log_println!("  ❌ Erreur: {}", e);
@@ -251,29 +218,39 @@ fn scan_sage_typelibs() -> SageResult<Vec<TypeLibInfo>> {
         }
     }
     
-    Ok(discovered_typelibs)
-}
-
-/// Scanne une Type Library spécifique
-fn scan_single_typelib(guid_str: &str) -> SageResult<Option<TypeLibInfo>> {
-    // Note: Cette fonction est un squelette pour l'instant
-    // Une implémentation complète nécessiterait d'utiliser LoadTypeLib et ITypeLib
-    // Ce qui est complexe en Rust avec windows-rs
-    
-    log_println!("    📚 Analyse de la TypeLib {}", guid_str);
+    // Export complet (enums, interfaces, signatures) pour consultation hors ligne
+    let export = serde_json::to_string_pretty(&discovered_typelibs)
+        .map_err(|e| e.to_string())
+        .and_then(|json| std::fs::write("sage_typelibs.json", json).map_err(|e| e.to_string()));
+    match export {
+        Ok(()) => {
+            log_println!("  💾 Export JSON: sage_typelibs.json");
+        }
+        Err(e) => {
+            log_println!("  ❌ Export JSON impossible: {}", e);
+        }
+    }
     
-    // Pour l'instant, retourner None car l'implémentation complète est complexe
-    // TODO: Implémenter l'analyse des TypeLib via ITypeLib
-    Ok(None)
+    Ok(discovered_typelibs)
 }
 
-/// Teste les ProgIDs connus pour découvrir des objets COM directement instanciables
+/// Teste les ProgIDs Sage enregistrés pour découvrir des objets COM directement instanciables
 fn test_known_progids() -> Vec<(String, bool, Option<ComObjectInfo>)> {
     let mut results = Vec::new();
     
-    log_println!("🧪 Test des ProgIDs connus...");
-    
-    for &progid in SAGE_PROGIDS {
+    log_println!("🧪 Test des ProgIDs enregistrés...");
+    let classes = discover_classes(&WindowsRegistry).unwrap_or_else(|e| {
+        log_println!("  ❌ Lecture du registre impossible: {}", e);
+        Vec::new()
+    });
+    
+    for class in &classes {
+        let progid = class.progid.as_str();
+        log_println!(
+            "  📌 {:?} v{}",
+            class.module,
+            class.version.map(|v| v.to_string()).unwrap_or_else(|| "?".to_string())
+        );
         log_println!("  🔍 Test de: {}", progid);
         
         match test_single_progid(progid) {
@@ -328,15 +305,24 @@ fn test_single_progid(progid: &str) -> SageResult<ComObjectInfo> {
 }
 
 /// Affiche un résumé des Type Libraries découvertes
-fn print_typelib_summary(typelibs: &[TypeLibInfo]) {
+fn print_typelib_summary(typelibs: &[TypeLibrary]) {
     log_println!("\n📚 === RÉSUMÉ DES TYPE LIBRARIES ===");
     if typelibs.is_empty() {
         log_println!("❌ Aucune Type Library découverte");
-        log_println!("💡 Note: L'analyse des TypeLib nécessite une implémentation plus avancée");
     } else {
         for typelib in typelibs {
-            log_println!("📚 {} (v{}) - {} interfaces", 
-                typelib.name, typelib.version, typelib.interfaces.len());
+            log_println!("📚 {} (v{}) {} - {} coclasses, {} dispinterfaces, {} enums, {} records",
+                typelib.name, typelib.version, typelib.guid,
+                typelib.of_kind(TypeKind::CoClass).count(),
+                typelib.of_kind(TypeKind::DispInterface).count(),
+                typelib.enums().count(),
+                typelib.of_kind(TypeKind::Record).count());
+            for enumeration in typelib.enums().filter(|e| !e.is_hidden) {
+                let values: Vec<String> = enumeration.values.iter()
+                    .map(|v| format!("{}={}", v.name, v.value))
+                    .collect();
+                log_println!("   🔢 {}: {}", enumeration.name, values.join(", "));
+            }
         }
     }
 }
@@ -623,7 +609,7 @@ fn analyze_members(
     if let Ok(method_list) = instance.list_methods_only() {
         for method in method_list {
             // Essayer d'extraire plus d'informations sur les paramètres
-            let parameters = extract_method_parameters(instance, &method.name, method.param_count);
+            let parameters = extract_method_parameters(&method);
             
             methods.push(MethodInfo {
                 name: method.name.clone(),
@@ -659,24 +645,17 @@ fn test_if_property_is_object(safe_dispatch: &SafeDispatch, property_name: &str)
     }
 }
 
-/// Extrait les informations sur les paramètres d'une méthode
-fn extract_method_parameters(
-    _instance: &ComInstance,
-    _method_name: &str,
-    param_count: Option<u32>,
-) -> Vec<ParameterInfo> {
-    // Pour l'instant, nous créons des paramètres génériques
-    // Une version plus avancée pourrait utiliser ITypeInfo pour obtenir les vrais types
-    match param_count {
-        Some(count) => {
-            (0..count).map(|i| ParameterInfo {
-                name: Some(format!("param{}", i)),
-                param_type: "VARIANT".to_string(), // Type générique pour l'instant
-                is_optional: false,
-            }).collect()
-        }
-        None => vec![],
-    }
+/// Extrait les informations sur les paramètres d'une méthode (issues du FUNCDESC)
+fn extract_method_parameters(method: &MemberInfo) -> Vec<ParameterInfo> {
+    method
+        .params
+        .iter()
+        .map(|p| ParameterInfo {
+            name: Some(p.name.clone()),
+            param_type: p.type_name.clone(),
+            is_optional: p.optional,
+        })
+        .collect()
 }
 
 /// Affiche un résumé de l'analyse
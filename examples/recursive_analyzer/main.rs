@@ -0,0 +1,18 @@
+#![recursion_limit = "256"]
+
+//! Analyseur récursif des objets COM Sage 100c
+//!
+//! L'analyse elle-même est dans `analyzer.rs`, compilé uniquement avec la couche COM.
+
+#[cfg(all(windows, feature = "com"))]
+mod analyzer;
+
+#[cfg(all(windows, feature = "com"))]
+fn main() -> objets_metier_rs::errors::SageResult<()> {
+    analyzer::main()
+}
+
+#[cfg(not(all(windows, feature = "com")))]
+fn main() {
+    eprintln!("❌ Cet exemple nécessite Windows et la feature `com` (Objets Métier Sage 100c)");
+}
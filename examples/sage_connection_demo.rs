@@ -1,11 +1,20 @@
+#[cfg(all(windows, feature = "com"))]
 use objets_metier_rs::wrappers::CptaApplication;
+#[cfg(all(windows, feature = "com"))]
+use objets_metier_rs::com::ComApartment;
+#[cfg(all(windows, feature = "com"))]
 use objets_metier_rs::errors::SageResult;
 
-const BSCPTA_CLSID: &str = "309DE0FB-9FB8-4F4E-8295-CC60C60DAA33";
+#[cfg(not(all(windows, feature = "com")))]
+fn main() {
+    eprintln!("❌ Cet exemple nécessite Windows et la feature `com` (Objets Métier Sage 100c)");
+}
 
+#[cfg(all(windows, feature = "com"))]
 fn main() -> SageResult<()> {
     println!("🚀 Sage 100c - Connexion automatique style C# v0.1.3");
     println!("═══════════════════════════════════════════════════════");
+    let _com = ComApartment::sta()?;
     
     // Reproduction exacte du code C# fourni par Sage
     demo_csharp_connection_style()?;
@@ -23,11 +32,12 @@ fn main() -> SageResult<()> {
 /// _mCpta.Loggable.UserPwd = "";
 /// _mCpta.Open();
 /// ```
+#[cfg(all(windows, feature = "com"))]
 fn demo_csharp_connection_style() -> SageResult<()> {
     println!("\n🎯 === REPRODUCTION EXACTE DU CODE C# SAGE ===");
     
     // Équivalent C# : private BSCPTAApplication100c _mCpta;
-    let app = CptaApplication::new(BSCPTA_CLSID)?;
+    let app = CptaApplication::newest_installed()?;
     println!("✅ BSCPTAApplication100c créée");
     
     // Équivalent C# : _mCpta.Name = "D:\\TMP\\BIJOU.MAE";
@@ -100,8 +110,9 @@ fn demo_csharp_connection_style() -> SageResult<()> {
 
 /// Fonction utilitaire pour tester différents chemins de base
 #[allow(dead_code)]
+#[cfg(all(windows, feature = "com"))]
 fn test_different_database_paths() -> SageResult<()> {
-    let app = CptaApplication::new(BSCPTA_CLSID)?;
+    let app = CptaApplication::newest_installed()?;
     
     let test_paths = vec![
         r"D:\TMP\BIJOU.MAE",
@@ -130,8 +141,9 @@ fn test_different_database_paths() -> SageResult<()> {
 
 /// Fonction utilitaire pour tester différents credentials
 #[allow(dead_code)] 
+#[cfg(all(windows, feature = "com"))]
 fn test_different_credentials() -> SageResult<()> {
-    let app = CptaApplication::new(BSCPTA_CLSID)?;
+    let app = CptaApplication::newest_installed()?;
     let loggable = app.loggable()?;
     
     let test_credentials = vec![
@@ -0,0 +1,159 @@
+//! Macros dérivées pour `objets_metier_rs`
+//!
+//! `#[derive(SageObject)]` implémente `SageRecord` et `FromDispatch` pour une
+//! structure dont chaque champ correspond à une propriété COM de l'objet Sage.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, LitStr, parse_macro_input, spanned::Spanned};
+
+/// Dérive `SageRecord` et `FromDispatch` à partir des champs d'une structure
+///
+/// Attributs de champ :
+/// - `#[sage(name = "CG_Num")]` : nom de la propriété COM (par défaut le nom du champ)
+/// - `#[sage(read_only)]` : lu mais jamais écrit (ex: `cbMarq`)
+/// - `#[sage(skip)]` : ignoré, initialisé avec `Default::default()`
+#[proc_macro_derive(SageObject, attributes(sage))]
+pub fn derive_sage_object(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input).unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+/// Options d'un champ issues de `#[sage(...)]`
+struct FieldOptions {
+    name: Option<String>,
+    read_only: bool,
+    skip: bool,
+}
+
+fn field_options(field: &syn::Field) -> syn::Result<FieldOptions> {
+    let mut options = FieldOptions {
+        name: None,
+        read_only: false,
+        skip: false,
+    };
+
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("sage")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                options.name = Some(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else if meta.path.is_ident("read_only") {
+                options.read_only = true;
+                Ok(())
+            } else if meta.path.is_ident("skip") {
+                options.skip = true;
+                Ok(())
+            } else {
+                Err(meta.error("attribut attendu: name = \"...\", read_only ou skip"))
+            }
+        })?;
+    }
+
+    Ok(options)
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new(
+                    input.span(),
+                    "SageObject nécessite une structure à champs nommés",
+                ));
+            }
+        },
+        _ => {
+            return Err(syn::Error::new(
+                input.span(),
+                "SageObject ne s'applique qu'aux structures",
+            ));
+        }
+    };
+
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            input.generics.span(),
+            "SageObject ne supporte pas les structures génériques",
+        ));
+    }
+    let ident = &input.ident;
+
+    let mut names = Vec::new();
+    let mut reads = Vec::new();
+    let mut writes = Vec::new();
+
+    for field in fields {
+        let options = field_options(field)?;
+        let field_ident = field.ident.as_ref().expect("champ nommé");
+        let ty = &field.ty;
+
+        if options.skip {
+            reads.push(quote! { #field_ident: ::core::default::Default::default() });
+            continue;
+        }
+
+        let property = options.name.unwrap_or_else(|| field_ident.to_string());
+        names.push(property.clone());
+
+        reads.push(quote! {
+            #field_ident: {
+                let value = dispatch
+                    .get_property_by_name(#property)
+                    .context(concat!("lecture de ", #property))?;
+                <#ty as ::objets_metier_rs::com::FromVariant>::from_variant(&value)
+                    .context(concat!("conversion de ", #property))?
+            }
+        });
+
+        if !options.read_only {
+            writes.push(quote! {
+                let value = ::objets_metier_rs::com::IntoVariant::into_variant(
+                    ::core::clone::Clone::clone(&self.#field_ident),
+                )
+                .context(concat!("conversion de ", #property))?;
+                dispatch
+                    .set_property_by_name(#property, value)
+                    .context(concat!("écriture de ", #property))?;
+            });
+        }
+    }
+
+    Ok(quote! {
+        impl ::objets_metier_rs::com::SageRecord for #ident {
+            fn property_names() -> &'static [&'static str] {
+                &[#(#names),*]
+            }
+
+            fn read_from<D: ::objets_metier_rs::com::DispatchBackend>(
+                object: &D,
+            ) -> ::objets_metier_rs::SageResult<Self> {
+                #[allow(unused_imports)]
+                use ::objets_metier_rs::ResultExt as _;
+                #[allow(unused_variables)]
+                let dispatch = ::objets_metier_rs::com::SafeDispatch::new(object);
+                Ok(#ident { #(#reads),* })
+            }
+
+            fn write_to<D: ::objets_metier_rs::com::DispatchBackend>(
+                &self,
+                object: &D,
+            ) -> ::objets_metier_rs::SageResult<()> {
+                #[allow(unused_imports)]
+                use ::objets_metier_rs::ResultExt as _;
+                #[allow(unused_variables)]
+                let dispatch = ::objets_metier_rs::com::SafeDispatch::new(object);
+                #(#writes)*
+                Ok(())
+            }
+        }
+
+        impl<D: ::objets_metier_rs::com::DispatchBackend> ::objets_metier_rs::com::FromDispatch<D> for #ident {
+            fn from_dispatch(dispatch: D) -> ::objets_metier_rs::SageResult<Self> {
+                <Self as ::objets_metier_rs::com::SageRecord>::read_from(&dispatch)
+            }
+        }
+    })
+}
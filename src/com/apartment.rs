@@ -0,0 +1,171 @@
+use crate::errors::{HResult, SageError, SageResult};
+use std::cell::Cell;
+use std::marker::PhantomData;
+
+#[cfg(all(windows, feature = "com"))]
+use windows::Win32::System::Com::{
+    CoInitializeEx, CoUninitialize, COINIT_APARTMENTTHREADED, COINIT_MULTITHREADED,
+};
+
+/// Modèle de threading COM du thread courant
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApartmentModel {
+    /// Single-Threaded Apartment (`COINIT_APARTMENTTHREADED`), requis par les Objets Métier
+    SingleThreaded,
+    /// Multi-Threaded Apartment (`COINIT_MULTITHREADED`)
+    MultiThreaded,
+}
+
+thread_local! {
+    /// Apartment du thread et nombre de gardes actives
+    static APARTMENT: Cell<Option<(ApartmentModel, usize)>> = const { Cell::new(None) };
+}
+
+/// Garde RAII de l'initialisation COM du thread courant
+///
+/// Le premier `ComApartment` du thread appelle `CoInitializeEx`, le dernier
+/// libéré appelle `CoUninitialize`. Chaque `ComInstance` détient une garde :
+/// COM reste initialisé tant qu'un objet vit, quel que soit l'ordre de
+/// libération des applications.
+///
+/// ```ignore
+/// let _com = ComApartment::sta()?;
+/// let cpta = CptaApplication::newest_installed()?;
+/// let cial = CialApplication::newest_installed()?;
+/// drop(cpta); // COM reste initialisé pour `cial`
+/// ```
+///
+/// La garde n'est ni `Send` ni `Sync` : elle est liée à son thread.
+#[derive(Debug)]
+pub struct ComApartment {
+    model: ApartmentModel,
+    _thread_bound: PhantomData<*const ()>,
+}
+
+impl ComApartment {
+    /// Entre dans un STA, le modèle attendu par les Objets Métier Sage
+    pub fn sta() -> SageResult<Self> {
+        Self::enter(ApartmentModel::SingleThreaded)
+    }
+
+    /// Entre dans le MTA
+    pub fn mta() -> SageResult<Self> {
+        Self::enter(ApartmentModel::MultiThreaded)
+    }
+
+    /// Initialise COM sur le thread, ou rejoint l'apartment déjà ouvert
+    ///
+    /// Demander un autre modèle que celui du thread échoue avec
+    /// `RPC_E_CHANGED_MODE`, comme `CoInitializeEx`.
+    pub fn enter(model: ApartmentModel) -> SageResult<Self> {
+        match APARTMENT.get() {
+            Some((current, count)) if current == model => APARTMENT.set(Some((model, count + 1))),
+            Some((current, _)) => {
+                return Err(SageError::from_code(
+                    HResult::RPC_E_CHANGED_MODE,
+                    &format!("Le thread est déjà initialisé en {:?}, {:?} demandé", current, model),
+                ));
+            }
+            None => {
+                initialize(model)?;
+                APARTMENT.set(Some((model, 1)));
+            }
+        }
+
+        Ok(ComApartment {
+            model,
+            _thread_bound: PhantomData,
+        })
+    }
+
+    /// Nouvelle garde sur l'apartment du thread, qui doit déjà être ouvert
+    ///
+    /// Erreur `CO_E_NOTINITIALIZED` si aucun `ComApartment` n'existe sur le thread.
+    pub fn join() -> SageResult<Self> {
+        match Self::current() {
+            Some(model) => Self::enter(model),
+            None => Err(SageError::from_code(
+                HResult::CO_E_NOTINITIALIZED,
+                "COM n'est pas initialisé sur ce thread : créez d'abord un ComApartment (ComApartment::sta())",
+            )),
+        }
+    }
+
+    /// Modèle de l'apartment ouvert sur le thread courant, s'il y en a un
+    pub fn current() -> Option<ApartmentModel> {
+        APARTMENT.get().map(|(model, _)| model)
+    }
+
+    pub fn model(&self) -> ApartmentModel {
+        self.model
+    }
+}
+
+impl Drop for ComApartment {
+    fn drop(&mut self) {
+        match APARTMENT.get() {
+            Some((model, count)) if count > 1 => APARTMENT.set(Some((model, count - 1))),
+            _ => {
+                APARTMENT.set(None);
+                uninitialize();
+            }
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "com"))]
+fn initialize(model: ApartmentModel) -> SageResult<()> {
+    let flags = match model {
+        ApartmentModel::SingleThreaded => COINIT_APARTMENTTHREADED,
+        ApartmentModel::MultiThreaded => COINIT_MULTITHREADED,
+    };
+    // S_FALSE (déjà initialisé par l'hôte) compte aussi et sera équilibré
+    unsafe { CoInitializeEx(None, flags).map_err(SageError::from) }
+}
+
+#[cfg(all(windows, feature = "com"))]
+fn uninitialize() {
+    unsafe { CoUninitialize() }
+}
+
+/// Sans COM, seule la comptabilité des gardes est tenue
+#[cfg(not(all(windows, feature = "com")))]
+fn initialize(_model: ApartmentModel) -> SageResult<()> {
+    Ok(())
+}
+
+#[cfg(not(all(windows, feature = "com")))]
+fn uninitialize() {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Chaque test s'exécute sur son propre thread : l'état est isolé
+
+    #[test]
+    fn test_guards_are_refcounted_per_thread() {
+        assert!(ComApartment::join().unwrap_err().is_com_not_initialized());
+
+        let outer = ComApartment::sta().unwrap();
+        let inner = ComApartment::join().unwrap();
+        assert_eq!(inner.model(), ApartmentModel::SingleThreaded);
+
+        drop(outer);
+        assert_eq!(ComApartment::current(), Some(ApartmentModel::SingleThreaded));
+        drop(inner);
+        assert_eq!(ComApartment::current(), None);
+
+        // Un autre thread n'hérite pas de l'apartment
+        let _sta = ComApartment::sta().unwrap();
+        std::thread::spawn(|| assert_eq!(ComApartment::current(), None)).join().unwrap();
+    }
+
+    #[test]
+    fn test_model_mismatch_is_rejected() {
+        let _mta = ComApartment::mta().unwrap();
+        let error = ComApartment::sta().unwrap_err();
+        assert!(matches!(error, SageError::ComError { hresult: HResult::RPC_E_CHANGED_MODE, .. }));
+        assert_eq!(ComApartment::current(), Some(ApartmentModel::MultiThreaded));
+    }
+}
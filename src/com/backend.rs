@@ -0,0 +1,229 @@
+use super::SafeVariant;
+use crate::errors::{SageError, SageResult};
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::cell::OnceCell;
+use std::fmt;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Type d'invocation IDispatch (équivalent des flags DISPATCH_*)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InvokeKind {
+    /// Appel de méthode - accepte aussi un PROPGET comme le fait VB
+    Method,
+    /// Lecture de propriété (PROPGET)
+    PropertyGet,
+    /// Écriture de propriété par valeur (PROPPUT)
+    PropertyPut,
+    /// Écriture de propriété par référence (PROPPUTREF)
+    PropertyPutRef,
+}
+
+/// Énumérateur d'une collection d'automation - ÉQUIVALENT IEnumVARIANT
+pub trait VariantEnumerator {
+    /// Lit jusqu'à `max` éléments ; un lot vide signale la fin de la collection
+    fn next_batch(&mut self, max: usize) -> SageResult<Vec<SafeVariant>>;
+}
+
+/// Backend d'automation sur lequel s'appuient `SafeDispatch` et les wrappers
+///
+/// L'implémentation COM (`IDispatch`) est utilisée en production, l'objet
+/// factice `FakeObject` permet d'exécuter la logique des wrappers sans Sage.
+pub trait DispatchBackend {
+    /// Résout les DISPID des noms donnés - ÉQUIVALENT GetIDsOfNames
+    fn get_ids_of_names(&self, names: &[&str]) -> SageResult<Vec<i32>>;
+
+    /// Invoque un membre par son DISPID - ÉQUIVALENT IDispatch::Invoke
+    ///
+    /// `params` est dans l'ordre naturel de la signature (de gauche à droite).
+    fn invoke(
+        &self,
+        dispid: i32,
+        name: &str,
+        kind: InvokeKind,
+        params: &[SafeVariant],
+    ) -> SageResult<SafeVariant>;
+
+    /// Identifiant de l'interface (GUID issu de l'ITypeInfo) servant de clé
+    /// au cache de DISPID ; `None` désactive le cache pour cet objet
+    fn interface_id(&self) -> Option<String> {
+        None
+    }
+
+    /// Ouvre un énumérateur sur l'objet s'il s'agit d'une collection
+    /// (membre `_NewEnum`, DISPID_NEWENUM)
+    fn new_enum(&self) -> SageResult<Box<dyn VariantEnumerator>> {
+        Err(SageError::UnsupportedOperation(
+            "L'objet n'est pas une collection énumérable".to_string(),
+        ))
+    }
+
+    /// Invoque un membre avec, en plus des paramètres positionnels, des
+    /// arguments nommés identifiés par le DISPID de leur paramètre
+    ///
+    /// Les DISPID des paramètres s'obtiennent avec `get_ids_of_names` en
+    /// passant le nom du membre suivi des noms de paramètres.
+    fn invoke_named(
+        &self,
+        dispid: i32,
+        name: &str,
+        kind: InvokeKind,
+        params: &[SafeVariant],
+        named: &[(i32, SafeVariant)],
+    ) -> SageResult<SafeVariant> {
+        if named.is_empty() {
+            self.invoke(dispid, name, kind, params)
+        } else {
+            Err(SageError::UnsupportedOperation(format!(
+                "Arguments nommés non supportés pour '{}'",
+                name
+            )))
+        }
+    }
+}
+
+/// Permet de retrouver le type concret d'un backend effacé
+trait AnyBackend: DispatchBackend {
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: DispatchBackend + 'static> AnyBackend for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Référence partagée vers un objet d'automation, quel que soit son backend
+#[derive(Clone)]
+pub struct DispatchObject {
+    inner: Rc<dyn AnyBackend>,
+    /// GUID d'interface, résolu une seule fois pour toutes les copies
+    interface_id: Rc<OnceCell<Option<String>>>,
+    /// Identifiant attribué à la construction, jamais réutilisé
+    handle: u64,
+}
+
+/// Prochain handle d'objet ; 0 n'est jamais attribué
+static NEXT_HANDLE: AtomicU64 = AtomicU64::new(1);
+
+impl DispatchObject {
+    /// Encapsule un backend concret
+    pub fn new<B: DispatchBackend + 'static>(backend: B) -> Self {
+        let inner: Rc<dyn AnyBackend> = Rc::new(backend);
+        let handle = match inner.as_any().downcast_ref::<DetachedDispatch>() {
+            Some(detached) => detached.handle(),
+            None => NEXT_HANDLE.fetch_add(1, Ordering::Relaxed),
+        };
+        DispatchObject {
+            inner,
+            interface_id: Rc::new(OnceCell::new()),
+            handle,
+        }
+    }
+
+    /// Retourne le backend concret s'il est du type demandé
+    pub fn downcast_ref<B: DispatchBackend + 'static>(&self) -> Option<&B> {
+        self.inner.as_any().downcast_ref::<B>()
+    }
+
+    /// Identifiant opaque de l'objet, partagé par toutes ses copies
+    ///
+    /// Sert à représenter l'objet lors de la sérialisation. Les handles sont
+    /// attribués dans l'ordre de création et restent uniques pour le
+    /// processus, même après libération de l'objet ; un objet relu
+    /// (`DetachedDispatch`) conserve le handle sous lequel il a été écrit.
+    pub fn handle(&self) -> u64 {
+        self.handle
+    }
+}
+
+/// Objet d'automation relu depuis une sérialisation : seul son handle est
+/// connu, tout appel échoue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DetachedDispatch {
+    handle: u64,
+}
+
+impl DetachedDispatch {
+    pub fn new(handle: u64) -> Self {
+        DetachedDispatch { handle }
+    }
+
+    /// Handle de l'objet d'origine
+    pub fn handle(&self) -> u64 {
+        self.handle
+    }
+}
+
+impl DispatchBackend for DetachedDispatch {
+    fn get_ids_of_names(&self, names: &[&str]) -> SageResult<Vec<i32>> {
+        Err(SageError::UnsupportedOperation(format!(
+            "Objet détaché #{}: résolution de {:?} impossible",
+            self.handle, names
+        )))
+    }
+
+    fn invoke(
+        &self,
+        _dispid: i32,
+        name: &str,
+        _kind: InvokeKind,
+        _params: &[SafeVariant],
+    ) -> SageResult<SafeVariant> {
+        Err(SageError::UnsupportedOperation(format!(
+            "Objet détaché #{}: appel de '{}' impossible",
+            self.handle, name
+        )))
+    }
+}
+
+impl DispatchBackend for DispatchObject {
+    fn get_ids_of_names(&self, names: &[&str]) -> SageResult<Vec<i32>> {
+        self.inner.get_ids_of_names(names)
+    }
+
+    fn new_enum(&self) -> SageResult<Box<dyn VariantEnumerator>> {
+        self.inner.new_enum()
+    }
+
+    fn interface_id(&self) -> Option<String> {
+        self.interface_id
+            .get_or_init(|| {
+                // Un IDispatch nu ne mémorise pas son GUID : il est lu ici, une fois
+                #[cfg(all(windows, feature = "com"))]
+                if let Some(dispatch) = self.downcast_ref::<windows::Win32::System::Com::IDispatch>() {
+                    return super::idispatch::type_interface_id(dispatch);
+                }
+                self.inner.interface_id()
+            })
+            .clone()
+    }
+
+    fn invoke(
+        &self,
+        dispid: i32,
+        name: &str,
+        kind: InvokeKind,
+        params: &[SafeVariant],
+    ) -> SageResult<SafeVariant> {
+        self.inner.invoke(dispid, name, kind, params)
+    }
+
+    fn invoke_named(
+        &self,
+        dispid: i32,
+        name: &str,
+        kind: InvokeKind,
+        params: &[SafeVariant],
+        named: &[(i32, SafeVariant)],
+    ) -> SageResult<SafeVariant> {
+        self.inner.invoke_named(dispid, name, kind, params, named)
+    }
+}
+
+impl fmt::Debug for DispatchObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DispatchObject").finish_non_exhaustive()
+    }
+}
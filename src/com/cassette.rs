@@ -0,0 +1,828 @@
+use super::backend::{DetachedDispatch, DispatchBackend, DispatchObject, InvokeKind, VariantEnumerator};
+use super::out_param::OutParam;
+use super::safe_array::{ArrayData, SafeArray};
+use super::SafeVariant;
+use crate::errors::{AutomationError, HResult, SageError, SageResult};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::Path;
+use std::rc::Rc;
+
+/// Erreur enregistrée dans une cassette
+///
+/// Les exceptions d'automation et les erreurs portant un HRESULT sont
+/// reclassées via le catalogue au rejeu ; toute autre erreur est restituée
+/// avec sa variante, ses champs et ses contextes, si bien que les prédicats
+/// (`is_class_not_registered()`...) répondent comme à l'enregistrement.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum RecordedError {
+    /// Exception d'automation (EXCEPINFO complet)
+    Automation(AutomationError),
+    /// Erreur portant un HRESULT, reclassée via le catalogue au rejeu
+    Com { hresult: HResult, message: String },
+    /// Toute autre erreur, sous sa forme sérialisée (`SageError`)
+    Other { error: serde_json::Value },
+}
+
+impl RecordedError {
+    fn from_error(error: &SageError) -> Self {
+        // Sous un contexte, l'erreur est conservée entière (voir `Other`)
+        if !matches!(error, SageError::Context { .. })
+            && let Some(automation) = error.as_automation()
+        {
+            return RecordedError::Automation(automation.clone());
+        }
+
+        match error {
+            SageError::ComError { hresult, message, .. }
+            | SageError::RecordLocked { hresult, message, .. }
+            | SageError::DuplicateKey { hresult, message, .. }
+            | SageError::RecordNotFound { hresult, message, .. }
+            | SageError::LicenseInvalid { hresult, message, .. }
+            | SageError::ClosedPeriod { hresult, message, .. } => RecordedError::Com {
+                hresult: *hresult,
+                message: message.clone(),
+            },
+            other => RecordedError::Other {
+                error: serde_json::to_value(other).unwrap_or_else(|e| {
+                    serde_json::json!({ "type": "InternalError", "details": format!("{}: {}", other, e) })
+                }),
+            },
+        }
+    }
+
+    fn to_error(&self, name: &str) -> SageError {
+        match self {
+            RecordedError::Automation(automation) => SageError::from_automation(automation.clone()),
+            RecordedError::Com { hresult, message } => SageError::from_code(*hresult, message),
+            RecordedError::Other { error } => serde_json::from_value(error.clone()).unwrap_or_else(|e| {
+                SageError::method_call(name, -1, &format!("Erreur enregistrée illisible : {}", e))
+            }),
+        }
+    }
+}
+
+/// Échange enregistré avec un objet d'automation
+///
+/// Les objets sont désignés par un numéro attribué dans l'ordre de leur
+/// apparition (0 pour le premier objet enregistré) ; dans les valeurs, y
+/// compris dans les tableaux et les paramètres `[in, out]`, ils sont écrits
+/// comme handle opaque (`{"type":"Dispatch","value":{"handle":3}}`). Un objet
+/// étranger à l'enregistrement reçoit son numéro à son premier passage en
+/// paramètre.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op")]
+pub enum CassetteEntry {
+    /// GetIDsOfNames
+    Names {
+        object: u64,
+        names: Vec<String>,
+        result: Result<Vec<i32>, RecordedError>,
+    },
+    /// IDispatch::Invoke ; `out` contient la valeur finale des paramètres [in, out]
+    Invoke {
+        object: u64,
+        dispid: i32,
+        name: String,
+        kind: InvokeKind,
+        params: Vec<SafeVariant>,
+        named: Vec<(i32, SafeVariant)>,
+        out: Vec<(usize, SafeVariant)>,
+        result: Result<SafeVariant, RecordedError>,
+    },
+    /// Ouverture d'un énumérateur (`_NewEnum`)
+    NewEnum {
+        object: u64,
+        result: Result<u64, RecordedError>,
+    },
+    /// Lot lu par `IEnumVARIANT::Next`
+    Next {
+        enumerator: u64,
+        max: usize,
+        result: Result<Vec<SafeVariant>, RecordedError>,
+    },
+}
+
+/// Trafic d'automation enregistré, sauvegardé en JSON
+///
+/// ```ignore
+/// // Chez le client
+/// let recorder = Recorder::new();
+/// let app = SageObject::new(recorder.record(DispatchObject::new(app_dispatch)));
+/// // ... scénario ...
+/// recorder.cassette().save("bijou_saisie.json")?;
+///
+/// // En test, sur n'importe quel OS
+/// let player = Player::new(Cassette::load("tests/cassettes/bijou_saisie.json")?);
+/// let app = SageObject::new(player.root());
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Cassette {
+    pub entries: Vec<CassetteEntry>,
+}
+
+impl Cassette {
+    /// Lit une cassette JSON
+    pub fn load<P: AsRef<Path>>(path: P) -> SageResult<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| cassette_error(path, e))?;
+        serde_json::from_str(&text).map_err(|e| cassette_error(path, e))
+    }
+
+    /// Écrit la cassette en JSON indenté
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> SageResult<()> {
+        let path = path.as_ref();
+        let text = serde_json::to_string_pretty(self).map_err(|e| cassette_error(path, e))?;
+        std::fs::write(path, text).map_err(|e| cassette_error(path, e))
+    }
+}
+
+fn cassette_error(path: &Path, error: impl std::fmt::Display) -> SageError {
+    SageError::InternalError(format!("Cassette {}: {}", path.display(), error))
+}
+
+/// Copie une valeur en remplaçant chacun de ses objets, y compris dans les
+/// tableaux de VARIANT et les paramètres `[in, out]` (copiés dans un nouvel
+/// emplacement)
+fn map_objects(value: &SafeVariant, map: &mut impl FnMut(&DispatchObject) -> DispatchObject) -> SafeVariant {
+    match value {
+        SafeVariant::Dispatch(object) => SafeVariant::Dispatch(map(object)),
+        SafeVariant::Array(array) => match array.data() {
+            ArrayData::Variant(items) => {
+                let items = items.iter().map(|item| map_objects(item, map)).collect();
+                SafeArray::new(array.bounds().to_vec(), ArrayData::Variant(items))
+                    .map_or_else(|_| value.clone(), SafeVariant::Array)
+            }
+            _ => value.clone(),
+        },
+        SafeVariant::ByRef(slot) => SafeVariant::ByRef(OutParam::new(map_objects(&slot.value(), map))),
+        other => other.clone(),
+    }
+}
+
+/// Remplace les objets d'une valeur par leur numéro de cassette
+fn detach(value: &SafeVariant, mut object_id: impl FnMut(&DispatchObject) -> u64) -> SafeVariant {
+    map_objects(value, &mut |object| DispatchObject::new(DetachedDispatch::new(object_id(object))))
+}
+
+struct RecorderState {
+    entries: Vec<CassetteEntry>,
+    next_id: u64,
+    /// Numéro attribué à chaque objet réel, par handle
+    ids: HashMap<u64, u64>,
+}
+
+/// Enregistre tous les échanges avec les objets qui lui sont confiés
+///
+/// Les objets renvoyés par les appels sont eux-mêmes enregistrés : toute la
+/// navigation à partir de l'objet racine est capturée.
+#[derive(Clone)]
+pub struct Recorder {
+    state: Rc<RefCell<RecorderState>>,
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Recorder {
+            state: Rc::new(RefCell::new(RecorderState {
+                entries: Vec::new(),
+                next_id: 0,
+                ids: HashMap::new(),
+            })),
+        }
+    }
+
+    /// Enveloppe un objet : les appels passent par l'enregistreur
+    ///
+    /// Un objet déjà vu (passé en paramètre, rendu à nouveau) garde son numéro.
+    pub fn record(&self, object: DispatchObject) -> DispatchObject {
+        DispatchObject::new(RecordingBackend {
+            id: self.object_id(&object),
+            inner: object,
+            recorder: self.clone(),
+        })
+    }
+
+    /// Copie des échanges enregistrés jusqu'ici
+    pub fn cassette(&self) -> Cassette {
+        Cassette { entries: self.state.borrow().entries.clone() }
+    }
+
+    fn next_id(&self) -> u64 {
+        let mut state = self.state.borrow_mut();
+        state.next_id += 1;
+        state.next_id - 1
+    }
+
+    /// Numéro de cassette d'un objet, attribué à sa première apparition
+    fn object_id(&self, object: &DispatchObject) -> u64 {
+        if let Some(id) = recording_id(object) {
+            return id;
+        }
+        if let Some(id) = self.state.borrow().ids.get(&object.handle()) {
+            return *id;
+        }
+        let id = self.next_id();
+        self.state.borrow_mut().ids.insert(object.handle(), id);
+        id
+    }
+
+    fn push(&self, entry: CassetteEntry) {
+        self.state.borrow_mut().entries.push(entry);
+    }
+
+    /// Enregistre les objets d'un résultat et renvoie (valeur pour l'appelant, valeur écrite)
+    ///
+    /// Un objet déjà vu (rendu tel quel dans un paramètre `[in, out]`) garde
+    /// son numéro.
+    fn wrap(&self, value: &SafeVariant) -> (SafeVariant, SafeVariant) {
+        let recorded = map_objects(value, &mut |object| match recording_id(object) {
+            Some(_) => object.clone(),
+            None => self.record(object.clone()),
+        });
+        let written = detach(&recorded, |object| self.object_id(object));
+        (recorded, written)
+    }
+
+    /// Valeur transmise à l'objet réel : les objets enregistrés y sont
+    /// remplacés par les objets réels, les objets étrangers reçoivent un numéro
+    fn unwrap(&self, value: &SafeVariant) -> SafeVariant {
+        map_objects(value, &mut |object| match object.downcast_ref::<RecordingBackend>() {
+            Some(recording) => recording.inner.clone(),
+            None => {
+                self.object_id(object);
+                object.clone()
+            }
+        })
+    }
+}
+
+fn recording_id(object: &DispatchObject) -> Option<u64> {
+    object.downcast_ref::<RecordingBackend>().map(|r| r.id)
+}
+
+/// Objet enregistré : délègue à l'objet réel et journalise chaque échange
+struct RecordingBackend {
+    id: u64,
+    inner: DispatchObject,
+    recorder: Recorder,
+}
+
+impl DispatchBackend for RecordingBackend {
+    fn get_ids_of_names(&self, names: &[&str]) -> SageResult<Vec<i32>> {
+        let result = self.inner.get_ids_of_names(names);
+        self.recorder.push(CassetteEntry::Names {
+            object: self.id,
+            names: names.iter().map(|n| n.to_string()).collect(),
+            result: result.as_ref().map(|ids| ids.clone()).map_err(RecordedError::from_error),
+        });
+        result
+    }
+
+    fn invoke(
+        &self,
+        dispid: i32,
+        name: &str,
+        kind: InvokeKind,
+        params: &[SafeVariant],
+    ) -> SageResult<SafeVariant> {
+        self.invoke_named(dispid, name, kind, params, &[])
+    }
+
+    // Pas d'`interface_id` : le cache de DISPID court-circuiterait des
+    // GetIDsOfNames dont le rejeu a besoin
+
+    fn new_enum(&self) -> SageResult<Box<dyn VariantEnumerator>> {
+        let result = self.inner.new_enum().map(|inner| RecordingEnumerator {
+            id: self.recorder.next_id(),
+            inner,
+            recorder: self.recorder.clone(),
+        });
+        self.recorder.push(CassetteEntry::NewEnum {
+            object: self.id,
+            result: result.as_ref().map(|e| e.id).map_err(RecordedError::from_error),
+        });
+        Ok(Box::new(result?))
+    }
+
+    fn invoke_named(
+        &self,
+        dispid: i32,
+        name: &str,
+        kind: InvokeKind,
+        params: &[SafeVariant],
+        named: &[(i32, SafeVariant)],
+    ) -> SageResult<SafeVariant> {
+        // L'objet réel reçoit les objets réels, dans ses propres emplacements
+        // [in, out], la cassette leurs numéros
+        let real_params: Vec<SafeVariant> = params.iter().map(|p| self.recorder.unwrap(p)).collect();
+        let real_named: Vec<(i32, SafeVariant)> =
+            named.iter().map(|(id, v)| (*id, self.recorder.unwrap(v))).collect();
+        let object_id = |object: &DispatchObject| self.recorder.object_id(object);
+        let recorded_params = params.iter().map(|p| detach(p, object_id)).collect();
+        let recorded_named = named.iter().map(|(id, v)| (*id, detach(v, object_id))).collect();
+
+        let result = if named.is_empty() {
+            self.inner.invoke(dispid, name, kind, &real_params)
+        } else {
+            self.inner.invoke_named(dispid, name, kind, &real_params, &real_named)
+        };
+
+        // Valeur finale des paramètres [in, out] recopiée chez l'appelant ; un
+        // objet qui y est écrit est enregistré comme un résultat
+        let out = params
+            .iter()
+            .zip(&real_params)
+            .enumerate()
+            .filter_map(|(i, (param, real))| {
+                let (slot, real) = (param.as_out_param()?, real.as_out_param()?);
+                let (value, written) = self.recorder.wrap(&real.value());
+                slot.set(value);
+                Some((i, written))
+            })
+            .collect();
+        for ((_, value), (_, real)) in named.iter().zip(&real_named) {
+            if let (Some(slot), Some(real)) = (value.as_out_param(), real.as_out_param()) {
+                slot.set(self.recorder.wrap(&real.value()).0);
+            }
+        }
+
+        let (result, recorded) = match result {
+            Ok(value) => {
+                let (value, written) = self.recorder.wrap(&value);
+                (Ok(value), Ok(written))
+            }
+            Err(error) => {
+                let recorded = RecordedError::from_error(&error);
+                (Err(error), Err(recorded))
+            }
+        };
+
+        self.recorder.push(CassetteEntry::Invoke {
+            object: self.id,
+            dispid,
+            name: name.to_string(),
+            kind,
+            params: recorded_params,
+            named: recorded_named,
+            out,
+            result: recorded,
+        });
+        result
+    }
+}
+
+struct RecordingEnumerator {
+    id: u64,
+    inner: Box<dyn VariantEnumerator>,
+    recorder: Recorder,
+}
+
+impl VariantEnumerator for RecordingEnumerator {
+    fn next_batch(&mut self, max: usize) -> SageResult<Vec<SafeVariant>> {
+        let result = self.inner.next_batch(max);
+        let (result, recorded) = match result {
+            Ok(items) => {
+                let (items, written): (Vec<_>, Vec<_>) = items.iter().map(|i| self.recorder.wrap(i)).unzip();
+                (Ok(items), Ok(written))
+            }
+            Err(error) => {
+                let recorded = RecordedError::from_error(&error);
+                (Err(error), Err(recorded))
+            }
+        };
+        self.recorder.push(CassetteEntry::Next { enumerator: self.id, max, result: recorded });
+        result
+    }
+}
+
+struct PlayerState {
+    entries: Vec<CassetteEntry>,
+    cursor: usize,
+}
+
+/// Rejoue une cassette : sert les réponses enregistrées, sans COM ni Sage
+///
+/// Les appels doivent se produire dans l'ordre de l'enregistrement ; les
+/// résolutions de noms peuvent être demandées à tout moment. Un appel qui
+/// ne correspond pas à la cassette échoue en indiquant l'échange attendu.
+#[derive(Clone)]
+pub struct Player {
+    state: Rc<RefCell<PlayerState>>,
+}
+
+impl Player {
+    pub fn new(cassette: Cassette) -> Self {
+        Player {
+            state: Rc::new(RefCell::new(PlayerState { entries: cassette.entries, cursor: 0 })),
+        }
+    }
+
+    /// Premier objet enregistré
+    pub fn root(&self) -> DispatchObject {
+        self.object(0)
+    }
+
+    /// Objet de numéro donné dans la cassette
+    pub fn object(&self, id: u64) -> DispatchObject {
+        DispatchObject::new(ReplayBackend { id, player: self.clone() })
+    }
+
+    /// Nombre d'appels enregistrés restant à rejouer
+    pub fn remaining(&self) -> usize {
+        let state = self.state.borrow();
+        state.entries[state.cursor..]
+            .iter()
+            .filter(|e| !matches!(e, CassetteEntry::Names { .. }))
+            .count()
+    }
+
+    /// Prochain échange (hors résolutions de noms), consommé s'il est accepté
+    fn next_entry<T>(&self, accept: impl FnOnce(&CassetteEntry) -> Option<T>, describe: &str) -> SageResult<T> {
+        let mut state = self.state.borrow_mut();
+        while let Some(CassetteEntry::Names { .. }) = state.entries.get(state.cursor) {
+            state.cursor += 1;
+        }
+        let expected = state.entries.get(state.cursor).ok_or_else(|| {
+            SageError::InternalError(format!("Cassette terminée : {} non enregistré", describe))
+        })?;
+        match accept(expected) {
+            Some(value) => {
+                state.cursor += 1;
+                Ok(value)
+            }
+            None => Err(SageError::InternalError(format!(
+                "Cassette : {} inattendu, échange n°{} enregistré : {}",
+                describe,
+                state.cursor,
+                describe_entry(expected)
+            ))),
+        }
+    }
+
+    /// Restitue les objets d'une valeur enregistrée
+    fn attach(&self, value: &SafeVariant) -> SafeVariant {
+        map_objects(value, &mut |object| self.object(object.handle()))
+    }
+}
+
+fn describe_entry(entry: &CassetteEntry) -> String {
+    match entry {
+        CassetteEntry::Names { object, names, .. } => format!("GetIDsOfNames({}) sur #{}", names.join(", "), object),
+        CassetteEntry::Invoke { object, name, kind, .. } => format!("{:?} {} sur #{}", kind, name, object),
+        CassetteEntry::NewEnum { object, .. } => format!("_NewEnum sur #{}", object),
+        CassetteEntry::Next { enumerator, .. } => format!("Next sur l'énumérateur #{}", enumerator),
+    }
+}
+
+fn replay_id(object: &DispatchObject) -> Option<u64> {
+    object.downcast_ref::<ReplayBackend>().map(|r| r.id)
+}
+
+/// Valeurs comparées sous leur forme sérialisée, les objets par numéro
+fn same_values(recorded: &[SafeVariant], actual: &[SafeVariant]) -> bool {
+    recorded.len() == actual.len() && recorded.iter().zip(actual).all(|(r, a)| same_value(r, a))
+}
+
+/// Un objet étranger au rejeu tient la place de l'objet étranger enregistré
+fn same_value(recorded: &SafeVariant, actual: &SafeVariant) -> bool {
+    match (recorded, actual) {
+        (SafeVariant::Dispatch(recorded), SafeVariant::Dispatch(actual)) => {
+            replay_id(actual).is_none_or(|id| id == recorded.handle())
+        }
+        (SafeVariant::Array(recorded), SafeVariant::Array(actual)) => match (recorded.data(), actual.data()) {
+            (ArrayData::Variant(r), ArrayData::Variant(a)) => {
+                recorded.bounds() == actual.bounds() && same_values(r, a)
+            }
+            _ => serde_json::to_value(recorded).ok() == serde_json::to_value(actual).ok(),
+        },
+        (SafeVariant::ByRef(recorded), SafeVariant::ByRef(actual)) => same_value(&recorded.value(), &actual.value()),
+        _ => serde_json::to_value(recorded).ok() == serde_json::to_value(actual).ok(),
+    }
+}
+
+/// Objet rejoué depuis une cassette
+struct ReplayBackend {
+    id: u64,
+    player: Player,
+}
+
+impl DispatchBackend for ReplayBackend {
+    fn get_ids_of_names(&self, names: &[&str]) -> SageResult<Vec<i32>> {
+        let state = self.player.state.borrow();
+        let recorded = state.entries.iter().find_map(|entry| match entry {
+            CassetteEntry::Names { object, names: recorded, result }
+                if *object == self.id && recorded.iter().map(String::as_str).eq(names.iter().copied()) =>
+            {
+                Some(result)
+            }
+            _ => None,
+        });
+        match recorded {
+            Some(Ok(ids)) => Ok(ids.clone()),
+            Some(Err(error)) => Err(error.to_error(&names.join(", "))),
+            None => Err(SageError::InternalError(format!(
+                "Cassette : GetIDsOfNames({}) non enregistré sur #{}",
+                names.join(", "),
+                self.id
+            ))),
+        }
+    }
+
+    fn invoke(
+        &self,
+        dispid: i32,
+        name: &str,
+        kind: InvokeKind,
+        params: &[SafeVariant],
+    ) -> SageResult<SafeVariant> {
+        self.invoke_named(dispid, name, kind, params, &[])
+    }
+
+    fn new_enum(&self) -> SageResult<Box<dyn VariantEnumerator>> {
+        let result = self.player.next_entry(
+            |entry| match entry {
+                CassetteEntry::NewEnum { object, result } if *object == self.id => Some(result.clone()),
+                _ => None,
+            },
+            &format!("_NewEnum sur #{}", self.id),
+        )?;
+        match result {
+            Ok(id) => Ok(Box::new(ReplayEnumerator { id, player: self.player.clone() })),
+            Err(error) => Err(error.to_error("_NewEnum")),
+        }
+    }
+
+    fn invoke_named(
+        &self,
+        _dispid: i32,
+        name: &str,
+        kind: InvokeKind,
+        params: &[SafeVariant],
+        named: &[(i32, SafeVariant)],
+    ) -> SageResult<SafeVariant> {
+        let (named_ids, named_values): (Vec<i32>, Vec<SafeVariant>) = named.iter().cloned().unzip();
+        let (out, result) = self.player.next_entry(
+            |entry| match entry {
+                CassetteEntry::Invoke {
+                    object,
+                    name: recorded_name,
+                    kind: recorded_kind,
+                    params: recorded_params,
+                    named: recorded_named,
+                    out,
+                    result,
+                    ..
+                } if *object == self.id
+                    && recorded_name == name
+                    && *recorded_kind == kind
+                    && same_values(recorded_params, params)
+                    && recorded_named.iter().map(|(id, _)| *id).eq(named_ids.iter().copied())
+                    && same_values(
+                        &recorded_named.iter().map(|(_, v)| v.clone()).collect::<Vec<_>>(),
+                        &named_values,
+                    ) =>
+                {
+                    Some((out.clone(), result.clone()))
+                }
+                _ => None,
+            },
+            &format!("{:?} {} sur #{}", kind, name, self.id),
+        )?;
+
+        for (index, value) in out {
+            if let Some(slot) = params.get(index).and_then(|p| p.as_out_param()) {
+                slot.set(self.player.attach(&value));
+            }
+        }
+        result
+            .map(|value| self.player.attach(&value))
+            .map_err(|error| error.to_error(name))
+    }
+}
+
+struct ReplayEnumerator {
+    id: u64,
+    player: Player,
+}
+
+impl VariantEnumerator for ReplayEnumerator {
+    fn next_batch(&mut self, max: usize) -> SageResult<Vec<SafeVariant>> {
+        let result = self.player.next_entry(
+            |entry| match entry {
+                CassetteEntry::Next { enumerator, max: recorded_max, result }
+                    if *enumerator == self.id && *recorded_max == max =>
+                {
+                    Some(result.clone())
+                }
+                _ => None,
+            },
+            &format!("Next sur l'énumérateur #{}", self.id),
+        )?;
+        result
+            .map(|items| items.iter().map(|i| self.player.attach(i)).collect())
+            .map_err(|error| error.to_error("Next"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::com::{FakeObject, SafeDispatch, SageObject};
+    use crate::wrappers::SageCollection;
+    use serde_json::json;
+
+    fn session() -> FakeObject {
+        let journal = FakeObject::new("IBOJournal3").with_property("JO_Intitule", "Ventes");
+        let journaux = FakeObject::new("IBICollection").with_items(vec![SafeVariant::from(journal.clone())]);
+        let factory = FakeObject::new("IBOJournalFactory3")
+            .with_property("List", journaux)
+            .with_method("ReadNumero", move |params| match params[0].to_string()?.as_str() {
+                "VTE" => Ok(SafeVariant::from(journal.clone())),
+                _ => Err(SageError::from_code(HResult(0x80040E19u32 as i32), "Journal introuvable")),
+            })
+            .with_method("Solde", |params| {
+                params[0].as_out_param().expect("[in, out]").set(1250.5);
+                Ok(SafeVariant::Bool(true))
+            })
+            .with_method("Dernier", move |params| {
+                let dernier = FakeObject::new("IBOJournal3").with_property("JO_Intitule", "Achats");
+                params[0].as_out_param().expect("[in, out]").set(dernier);
+                Ok(SafeVariant::Bool(true))
+            });
+        FakeObject::new("IBSCPTAApplication3").with_property("FactoryJournal", factory)
+    }
+
+    /// Scénario exécuté à l'identique à l'enregistrement et au rejeu
+    fn scenario(app: DispatchObject) -> SageResult<(String, f64, String, usize, bool)> {
+        let factory = SageObject::new(app).child("FactoryJournal")?;
+        let intitule = factory.call_object("ReadNumero", &["VTE".into()])?.as_str("JO_Intitule")?;
+        let solde = OutParam::new(SafeVariant::R8(0.0));
+        SafeDispatch::new(&factory).call_method_by_name("Solde", &[solde.as_param()])?;
+        // Objet rendu par un paramètre [in, out] : ses appels sont enregistrés
+        let dernier = OutParam::new(SafeVariant::Empty);
+        SafeDispatch::new(&factory).call_method_by_name("Dernier", &[dernier.as_param()])?;
+        let dernier = SageObject::from_variant(&dernier.value())?.as_str("JO_Intitule")?;
+        let journaux = SageCollection::from_variant(&factory.get("List")?)?;
+        let count = journaux.iter().collect::<SageResult<Vec<_>>>()?.len();
+        let not_found = factory.call("ReadNumero", &["ACH".into()]).unwrap_err().is_not_found();
+        Ok((intitule, solde.value().to_f64()?, dernier, count, not_found))
+    }
+
+    #[test]
+    fn test_record_then_replay() {
+        let recorder = Recorder::new();
+        let recorded = scenario(recorder.record(SafeVariant::from(session()).to_object().unwrap())).unwrap();
+        assert_eq!(recorded, ("Ventes".to_string(), 1250.5, "Achats".to_string(), 1, true));
+
+        let path = std::env::temp_dir().join(format!("objets_metier_cassette_{}.json", std::process::id()));
+        recorder.cassette().save(&path).unwrap();
+        let cassette = Cassette::load(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let player = Player::new(cassette);
+        assert_eq!(scenario(player.root()).unwrap(), recorded);
+        assert_eq!(player.remaining(), 0);
+    }
+
+    /// Intitulés des journaux d'un tableau d'objets
+    fn intitules(array: &SafeVariant) -> SageResult<Vec<String>> {
+        array.to_array()?.to_variants().iter().map(|j| SageObject::from_variant(j)?.as_str("JO_Intitule")).collect()
+    }
+
+    fn replay(recorder: &Recorder) -> Player {
+        let json = serde_json::to_string(&recorder.cassette()).unwrap();
+        Player::new(serde_json::from_str(&json).unwrap())
+    }
+
+    #[test]
+    fn test_record_then_replay_object_arrays() {
+        let journal = |intitule: &str| SafeVariant::from(FakeObject::new("IBOJournal3").with_property("JO_Intitule", intitule));
+        let journaux = SafeVariant::Array(SafeArray::from_data(ArrayData::Variant(vec![journal("Ventes"), journal("Achats")])));
+        let session = FakeObject::new("IBOJournalFactory3")
+            .with_method("Journaux", move |_| Ok(journaux.clone()))
+            .with_method("Dernier", |params| {
+                let journaux = params[0].to_array()?.to_variants();
+                let dernier = journaux.last().cloned().unwrap_or(SafeVariant::Null);
+                // L'objet réel, jamais l'enveloppe d'enregistrement
+                assert!(dernier.to_object()?.downcast_ref::<FakeObject>().is_some());
+                Ok(dernier)
+            });
+
+        let scenario = |factory: DispatchObject| -> SageResult<(Vec<String>, String)> {
+            let factory = SageObject::new(factory);
+            let journaux = factory.call("Journaux", &[])?;
+            let dernier = factory.call_object("Dernier", std::slice::from_ref(&journaux))?.as_str("JO_Intitule")?;
+            Ok((intitules(&journaux)?, dernier))
+        };
+
+        let recorder = Recorder::new();
+        let recorded = scenario(recorder.record(SafeVariant::from(session).to_object().unwrap())).unwrap();
+        assert_eq!(recorded, (vec!["Ventes".to_string(), "Achats".to_string()], "Achats".to_string()));
+
+        let player = replay(&recorder);
+        assert_eq!(scenario(player.root()).unwrap(), recorded);
+        assert_eq!(player.remaining(), 0);
+    }
+
+    #[test]
+    fn test_out_slot_reused_across_calls() {
+        let session = FakeObject::new("IBOJournalFactory3")
+            .with_method("Dernier", |params| {
+                let dernier = FakeObject::new("IBOJournal3").with_property("JO_Intitule", "Achats");
+                params[0].as_out_param().expect("[in, out]").set(dernier);
+                Ok(SafeVariant::Bool(true))
+            })
+            .with_method("Relire", |params| {
+                // Le même emplacement revient avec l'objet réel qui y a été écrit
+                let journal = params[0].as_out_param().expect("[in, out]").value().to_object()?;
+                assert!(journal.downcast_ref::<FakeObject>().is_some());
+                SageObject::new(journal).get("JO_Intitule")
+            });
+
+        let scenario = |factory: DispatchObject| -> SageResult<(String, String)> {
+            let factory = SafeDispatch::new(&factory);
+            let slot = OutParam::new(SafeVariant::Empty);
+            factory.call_method_by_name("Dernier", &[slot.as_param()])?;
+            let relu = factory.call_method_by_name("Relire", &[slot.as_param()])?.to_string()?;
+            Ok((relu, SageObject::from_variant(&slot.value())?.as_str("JO_Intitule")?))
+        };
+
+        let recorder = Recorder::new();
+        let recorded = scenario(recorder.record(SafeVariant::from(session).to_object().unwrap())).unwrap();
+        assert_eq!(recorded, ("Achats".to_string(), "Achats".to_string()));
+
+        // L'objet garde son numéro d'un appel à l'autre
+        let cassette = serde_json::to_value(recorder.cassette()).unwrap();
+        let relire = cassette["entries"].as_array().unwrap().iter().find(|e| e["name"] == "Relire").unwrap();
+        assert_eq!(relire["params"][0]["value"], json!({"type": "Dispatch", "value": {"handle": 1}}));
+
+        let player = replay(&recorder);
+        assert_eq!(scenario(player.root()).unwrap(), recorded);
+        assert_eq!(player.remaining(), 0);
+    }
+
+    #[test]
+    fn test_foreign_object_gets_own_number() {
+        let session = FakeObject::new("IBSCPTAApplication3").with_method("Associer", |params| Ok(params[0].clone()));
+        let scenario = |app: DispatchObject| -> SageResult<String> {
+            let banque = SafeVariant::from(FakeObject::new("IBOBanque3").with_property("BQ_Intitule", "Banque"));
+            SageObject::new(app).call_object("Associer", &[banque])?.as_str("BQ_Intitule")
+        };
+
+        let recorder = Recorder::new();
+        let recorded = scenario(recorder.record(SafeVariant::from(session).to_object().unwrap())).unwrap();
+        assert_eq!(recorded, "Banque");
+
+        // Numéroté comme les objets enregistrés, sans collision avec la racine
+        let cassette = serde_json::to_value(recorder.cassette()).unwrap();
+        let associer = cassette["entries"].as_array().unwrap().iter().find(|e| e["name"] == "Associer").unwrap();
+        let handle = json!({"type": "Dispatch", "value": {"handle": 1}});
+        assert_eq!(associer["params"][0], handle);
+        assert_eq!(associer["result"]["Ok"], handle);
+
+        let player = replay(&recorder);
+        assert_eq!(scenario(player.root()).unwrap(), recorded);
+        assert_eq!(player.remaining(), 0);
+    }
+
+    #[test]
+    fn test_replay_rejects_unexpected_call() {
+        let recorder = Recorder::new();
+        let app = SageObject::new(recorder.record(SafeVariant::from(session()).to_object().unwrap()));
+        app.child("FactoryJournal").unwrap().call("ReadNumero", &["VTE".into()]).unwrap();
+
+        let player = Player::new(recorder.cassette());
+        let factory = SageObject::new(player.root()).child("FactoryJournal").unwrap();
+        let error = factory.call("ReadNumero", &["ACH".into()]).unwrap_err();
+        assert!(error.to_string().contains("Method ReadNumero sur #1"), "{}", error);
+        assert_eq!(player.remaining(), 1);
+    }
+
+    #[test]
+    fn test_replay_restores_error_kind_and_contexts() {
+        let session = FakeObject::new("IBSCPTAApplication3").with_method("Open", |_| {
+            Err(SageError::class_not_registered("Objets100c.Cpta.Stream.1").context("ouverture de BIJOU.MAE"))
+        });
+        let recorder = Recorder::new();
+        let app = SageObject::new(recorder.record(SafeVariant::from(session).to_object().unwrap()));
+        let recorded = app.call("Open", &[]).unwrap_err();
+
+        let cassette: Cassette = serde_json::from_str(&serde_json::to_string(&recorder.cassette()).unwrap()).unwrap();
+        let replayed = SageObject::new(Player::new(cassette).root()).call("Open", &[]).unwrap_err();
+
+        assert!(replayed.is_class_not_registered());
+        assert_eq!(replayed.code(), recorded.code());
+        assert!(replayed.contexts().contains(&"ouverture de BIJOU.MAE"));
+        assert_eq!(replayed.contexts(), recorded.contexts());
+        assert_eq!(replayed.to_string(), recorded.to_string());
+    }
+}
@@ -0,0 +1,282 @@
+use super::backend::{DispatchBackend, DispatchObject};
+use super::SafeVariant;
+use crate::errors::{SageError, SageResult};
+use chrono::{NaiveDate, NaiveDateTime};
+use rust_decimal::Decimal;
+
+/// Conversion d'une valeur d'automation vers un type Rust
+///
+/// Implémenté pour les primitives, `String`, `Decimal`, les dates chrono,
+/// `Option<T>` (VT_EMPTY / VT_NULL donnent `None`) et `DispatchObject`.
+pub trait FromVariant: Sized {
+    fn from_variant(value: &SafeVariant) -> SageResult<Self>;
+}
+
+/// Conversion d'un type Rust vers une valeur d'automation
+///
+/// Faillible : une date hors de la plage OLE ou un montant hors de la plage
+/// CY ne peuvent pas être transmis à Sage.
+pub trait IntoVariant {
+    fn into_variant(self) -> SageResult<SafeVariant>;
+}
+
+/// Enregistrement Rust lu et écrit propriété par propriété sur un objet Sage
+///
+/// S'implémente avec `#[derive(SageObject)]` :
+///
+/// ```ignore
+/// #[derive(SageObject)]
+/// struct CompteG {
+///     #[sage(name = "CG_Num")]
+///     numero: String,
+///     #[sage(name = "CG_Intitule")]
+///     intitule: String,
+///     #[sage(name = "cbMarq", read_only)]
+///     marqueur: i32,
+/// }
+///
+/// let compte = CompteG::read_from(&objet)?;
+/// ```
+pub trait SageRecord: Sized {
+    /// Noms des propriétés COM lues, dans l'ordre des champs
+    fn property_names() -> &'static [&'static str];
+
+    /// Lit toutes les propriétés de l'objet
+    fn read_from<D: DispatchBackend>(object: &D) -> SageResult<Self>;
+
+    /// Écrit les propriétés modifiables (hors `read_only`) sur l'objet
+    fn write_to<D: DispatchBackend>(&self, object: &D) -> SageResult<()>;
+}
+
+fn conversion_error(value: &SafeVariant, to_type: &str) -> SageError {
+    SageError::ConversionError {
+        from_type: value.type_name().to_string(),
+        to_type: to_type.to_string(),
+        value: format!("{:?}", value),
+    }
+}
+
+/// Entier 64 bits signé, sans perte pour VT_I8
+fn to_i64(value: &SafeVariant) -> SageResult<i64> {
+    match value {
+        SafeVariant::I8(i) => Ok(*i),
+        SafeVariant::UI8(i) => i64::try_from(*i).map_err(|_| conversion_error(value, "i64")),
+        SafeVariant::UI4(i) => Ok(*i as i64),
+        SafeVariant::I1(i) => Ok(*i as i64),
+        SafeVariant::UI1(i) => Ok(*i as i64),
+        SafeVariant::BStr(s) => s.parse::<i64>().map_err(|_| conversion_error(value, "i64")),
+        _ => value.to_i32().map(i64::from),
+    }
+}
+
+macro_rules! integer_variant {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl FromVariant for $ty {
+                fn from_variant(value: &SafeVariant) -> SageResult<Self> {
+                    match value {
+                        SafeVariant::$variant(i) => Ok(*i),
+                        _ => <$ty>::try_from(to_i64(value)?)
+                            .map_err(|_| conversion_error(value, stringify!($ty))),
+                    }
+                }
+            }
+
+            impl IntoVariant for $ty {
+                fn into_variant(self) -> SageResult<SafeVariant> {
+                    Ok(SafeVariant::$variant(self))
+                }
+            }
+        )*
+    };
+}
+
+integer_variant!(
+    i8 => I1,
+    u8 => UI1,
+    i16 => I2,
+    u16 => UI2,
+    i32 => I4,
+    u32 => UI4,
+    i64 => I8,
+);
+
+impl FromVariant for u64 {
+    fn from_variant(value: &SafeVariant) -> SageResult<Self> {
+        match value {
+            SafeVariant::UI8(i) => Ok(*i),
+            _ => u64::try_from(to_i64(value)?).map_err(|_| conversion_error(value, "u64")),
+        }
+    }
+}
+
+impl IntoVariant for u64 {
+    fn into_variant(self) -> SageResult<SafeVariant> {
+        Ok(SafeVariant::UI8(self))
+    }
+}
+
+impl FromVariant for f32 {
+    fn from_variant(value: &SafeVariant) -> SageResult<Self> {
+        match value {
+            SafeVariant::R4(f) => Ok(*f),
+            _ => value.to_f64().map(|f| f as f32),
+        }
+    }
+}
+
+impl IntoVariant for f32 {
+    fn into_variant(self) -> SageResult<SafeVariant> {
+        Ok(SafeVariant::R4(self))
+    }
+}
+
+impl FromVariant for f64 {
+    fn from_variant(value: &SafeVariant) -> SageResult<Self> {
+        value.to_f64()
+    }
+}
+
+impl IntoVariant for f64 {
+    fn into_variant(self) -> SageResult<SafeVariant> {
+        Ok(SafeVariant::R8(self))
+    }
+}
+
+impl FromVariant for bool {
+    fn from_variant(value: &SafeVariant) -> SageResult<Self> {
+        value.to_bool()
+    }
+}
+
+impl IntoVariant for bool {
+    fn into_variant(self) -> SageResult<SafeVariant> {
+        Ok(SafeVariant::Bool(self))
+    }
+}
+
+impl FromVariant for String {
+    fn from_variant(value: &SafeVariant) -> SageResult<Self> {
+        value.to_string()
+    }
+}
+
+impl IntoVariant for String {
+    fn into_variant(self) -> SageResult<SafeVariant> {
+        Ok(SafeVariant::BStr(self))
+    }
+}
+
+impl IntoVariant for &str {
+    fn into_variant(self) -> SageResult<SafeVariant> {
+        Ok(SafeVariant::BStr(self.to_string()))
+    }
+}
+
+impl FromVariant for Decimal {
+    fn from_variant(value: &SafeVariant) -> SageResult<Self> {
+        value.to_decimal()
+    }
+}
+
+impl IntoVariant for Decimal {
+    fn into_variant(self) -> SageResult<SafeVariant> {
+        Ok(SafeVariant::Decimal(self))
+    }
+}
+
+impl FromVariant for NaiveDateTime {
+    fn from_variant(value: &SafeVariant) -> SageResult<Self> {
+        value.to_naive_datetime()
+    }
+}
+
+impl IntoVariant for NaiveDateTime {
+    fn into_variant(self) -> SageResult<SafeVariant> {
+        SafeVariant::from_naive_datetime(self)
+    }
+}
+
+impl FromVariant for NaiveDate {
+    fn from_variant(value: &SafeVariant) -> SageResult<Self> {
+        value.to_naive_date()
+    }
+}
+
+impl IntoVariant for NaiveDate {
+    fn into_variant(self) -> SageResult<SafeVariant> {
+        SafeVariant::from_naive_date(self)
+    }
+}
+
+impl FromVariant for DispatchObject {
+    fn from_variant(value: &SafeVariant) -> SageResult<Self> {
+        value.to_object()
+    }
+}
+
+impl IntoVariant for DispatchObject {
+    fn into_variant(self) -> SageResult<SafeVariant> {
+        Ok(SafeVariant::Dispatch(self))
+    }
+}
+
+impl FromVariant for SafeVariant {
+    fn from_variant(value: &SafeVariant) -> SageResult<Self> {
+        Ok(value.clone())
+    }
+}
+
+impl IntoVariant for SafeVariant {
+    fn into_variant(self) -> SageResult<SafeVariant> {
+        Ok(self)
+    }
+}
+
+/// VT_EMPTY, VT_NULL et paramètre omis donnent `None`
+impl<T: FromVariant> FromVariant for Option<T> {
+    fn from_variant(value: &SafeVariant) -> SageResult<Self> {
+        match value {
+            SafeVariant::Empty | SafeVariant::Null | SafeVariant::Missing => Ok(None),
+            _ => T::from_variant(value).map(Some),
+        }
+    }
+}
+
+/// `None` est transmis en VT_NULL
+impl<T: IntoVariant> IntoVariant for Option<T> {
+    fn into_variant(self) -> SageResult<SafeVariant> {
+        match self {
+            Some(value) => value.into_variant(),
+            None => Ok(SafeVariant::Null),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_primitive_conversions() {
+        assert_eq!(i16::from_variant(&SafeVariant::I4(-12)).unwrap(), -12);
+        assert!(u8::from_variant(&SafeVariant::I4(300)).is_err());
+        assert_eq!(i64::from_variant(&SafeVariant::I8(i64::MAX)).unwrap(), i64::MAX);
+        assert_eq!(u64::from_variant(&SafeVariant::from("42")).unwrap(), 42);
+        assert!(bool::from_variant(&SafeVariant::I2(-1)).unwrap());
+        assert!(matches!(7u16.into_variant().unwrap(), SafeVariant::UI2(7)));
+    }
+
+    #[test]
+    fn test_option_and_dates() {
+        assert_eq!(Option::<String>::from_variant(&SafeVariant::Null).unwrap(), None);
+        assert_eq!(Option::<i32>::from_variant(&SafeVariant::I4(3)).unwrap(), Some(3));
+        assert!(matches!(None::<i32>.into_variant().unwrap(), SafeVariant::Null));
+
+        let date = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+        let value = date.into_variant().unwrap();
+        assert!(matches!(value, SafeVariant::Date(_)));
+        assert_eq!(NaiveDate::from_variant(&value).unwrap(), date);
+        assert!(NaiveDate::from_ymd_opt(1, 1, 1).unwrap().into_variant().is_err());
+    }
+}
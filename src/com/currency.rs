@@ -0,0 +1,75 @@
+use crate::errors::{SageError, SageResult};
+use rust_decimal::{Decimal, RoundingStrategy};
+
+/// Nombre de décimales d'un CY COM (montant stocké en dix-millièmes)
+pub const CURRENCY_SCALE: u32 = 4;
+
+/// Mode d'arrondi appliqué lors du passage d'un décimal vers un CY
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoundingMode {
+    /// Arrondi au pair (bancaire) - comportement de VarCyFromDec
+    #[default]
+    Bankers,
+    /// Arrondi commercial : 0,5 s'éloigne de zéro
+    HalfAwayFromZero,
+    /// Troncature vers zéro
+    Truncate,
+}
+
+impl RoundingMode {
+    fn strategy(self) -> RoundingStrategy {
+        match self {
+            RoundingMode::Bankers => RoundingStrategy::MidpointNearestEven,
+            RoundingMode::HalfAwayFromZero => RoundingStrategy::MidpointAwayFromZero,
+            RoundingMode::Truncate => RoundingStrategy::ToZero,
+        }
+    }
+}
+
+/// Convertit un CY (dix-millièmes) en décimal exact
+pub fn currency_to_decimal(cy: i64) -> Decimal {
+    Decimal::new(cy, CURRENCY_SCALE)
+}
+
+/// Convertit un décimal en CY, en arrondissant à 4 décimales
+pub fn decimal_to_currency(value: Decimal, mode: RoundingMode) -> SageResult<i64> {
+    let mut rounded = value.round_dp_with_strategy(CURRENCY_SCALE, mode.strategy());
+    rounded.rescale(CURRENCY_SCALE);
+
+    i64::try_from(rounded.mantissa()).map_err(|_| SageError::ConversionError {
+        from_type: "Decimal".to_string(),
+        to_type: "Currency".to_string(),
+        value: value.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_currency_roundtrip_is_exact() {
+        let amount = currency_to_decimal(1_234_567_891);
+        assert_eq!(amount.to_string(), "123456.7891");
+        assert_eq!(decimal_to_currency(amount, RoundingMode::Bankers).unwrap(), 1_234_567_891);
+    }
+
+    #[test]
+    fn test_rounding_modes() {
+        let half = Decimal::from_str("0.00005").unwrap();
+        let odd_half = Decimal::from_str("0.00015").unwrap();
+        let negative = Decimal::from_str("-2.71828").unwrap();
+
+        assert_eq!(decimal_to_currency(half, RoundingMode::Bankers).unwrap(), 0);
+        assert_eq!(decimal_to_currency(odd_half, RoundingMode::Bankers).unwrap(), 2);
+        assert_eq!(decimal_to_currency(half, RoundingMode::HalfAwayFromZero).unwrap(), 1);
+        assert_eq!(decimal_to_currency(negative, RoundingMode::Truncate).unwrap(), -27182);
+    }
+
+    #[test]
+    fn test_currency_overflow() {
+        let too_big = Decimal::from_str("1000000000000000").unwrap();
+        assert!(decimal_to_currency(too_big, RoundingMode::Bankers).is_err());
+    }
+}
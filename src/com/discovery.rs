@@ -0,0 +1,181 @@
+use super::registry::RegistryReader;
+use crate::errors::{SageError, SageResult};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Module Sage exposé par une classe d'application Objets Métier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum SageModule {
+    /// Comptabilité (BSCPTAApplication100c)
+    Cpta,
+    /// Gestion commerciale (BSCIALApplication100c)
+    Cial,
+}
+
+impl SageModule {
+    /// Reconnaît le module d'après un ProgID (`Objets100c.Cpta.Stream.1`,
+    /// `BSCIALApplication100c`...)
+    fn from_progid(progid: &str) -> Option<Self> {
+        let lower = progid.to_lowercase();
+        let mut segments = lower.split('.');
+        let first = segments.next().unwrap_or_default();
+
+        if first.starts_with("objets100") {
+            match segments.next() {
+                Some("cpta") => Some(SageModule::Cpta),
+                Some("cial") => Some(SageModule::Cial),
+                _ => None,
+            }
+        } else if first.starts_with("bscptaapplication") {
+            Some(SageModule::Cpta)
+        } else if first.starts_with("bscialapplication") {
+            Some(SageModule::Cial)
+        } else {
+            None
+        }
+    }
+}
+
+/// Version d'une bibliothèque de types (clé `TypeLib\{GUID}\<major>.<minor>`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct SageVersion {
+    pub major: u16,
+    pub minor: u16,
+}
+
+impl SageVersion {
+    /// Analyse une version de TypeLib ; le registre l'écrit en hexadécimal (`b.0`)
+    pub fn parse(text: &str) -> Option<Self> {
+        let (major, minor) = text.split_once('.')?;
+        Some(SageVersion {
+            major: u16::from_str_radix(major, 16).ok()?,
+            minor: u16::from_str_radix(minor, 16).ok()?,
+        })
+    }
+}
+
+impl fmt::Display for SageVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// Classe d'application Objets Métier enregistrée sur le poste
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SageClassInfo {
+    pub module: SageModule,
+    pub progid: String,
+    /// CLSID avec accolades, tel qu'enregistré
+    pub clsid: String,
+    /// GUID de la bibliothèque de types associée (clé `TypeLib` du CLSID)
+    pub typelib: Option<String>,
+    /// Version de la bibliothèque de types associée, si elle est déclarée
+    pub version: Option<SageVersion>,
+    /// DLL du serveur (`InprocServer32`)
+    pub server: Option<String>,
+}
+
+/// Liste les classes d'application Sage enregistrées, la plus récente d'abord
+///
+/// Les ProgID `Objets100*.Cpta.*`/`Objets100*.Cial.*` et
+/// `BSCPTAApplication*`/`BSCIALApplication*` sont retenus ; un CLSID
+/// enregistré sous plusieurs ProgID n'apparaît qu'une fois (ProgID versionné
+/// de préférence).
+pub fn discover_classes(registry: &dyn RegistryReader) -> SageResult<Vec<SageClassInfo>> {
+    let mut classes: Vec<SageClassInfo> = Vec::new();
+
+    for progid in registry.subkeys("")? {
+        let Some(module) = SageModule::from_progid(&progid) else {
+            continue;
+        };
+        let Some(clsid) = registry.default_value(&format!(r"{}\CLSID", progid))? else {
+            continue;
+        };
+
+        let class_key = format!(r"CLSID\{}", clsid);
+        let server = registry.default_value(&format!(r"{}\InprocServer32", class_key))?;
+        let typelib = registry.default_value(&format!(r"{}\TypeLib", class_key))?;
+        let version = match &typelib {
+            Some(typelib) => registry
+                .subkeys(&format!(r"TypeLib\{}", typelib))?
+                .iter()
+                .filter_map(|v| SageVersion::parse(v))
+                .max(),
+            None => None,
+        };
+
+        let info = SageClassInfo { module, progid, clsid, typelib, version, server };
+        match classes.iter_mut().find(|c| c.clsid.eq_ignore_ascii_case(&info.clsid)) {
+            // `Objets100c.Cpta.Stream.1` plutôt que `Objets100c.Cpta.Stream`
+            Some(existing) if progid_revision(&info.progid) > progid_revision(&existing.progid) => *existing = info,
+            Some(_) => {}
+            None => classes.push(info),
+        }
+    }
+
+    classes.sort_by(|a, b| {
+        (b.version, progid_revision(&b.progid), &b.progid).cmp(&(a.version, progid_revision(&a.progid), &a.progid))
+    });
+    Ok(classes)
+}
+
+/// Classe la plus récente installée pour un module
+pub fn newest_class(registry: &dyn RegistryReader, module: SageModule) -> SageResult<SageClassInfo> {
+    discover_classes(registry)?
+        .into_iter()
+        .find(|c| c.module == module)
+        .ok_or_else(|| SageError::class_not_registered(format!("Aucune application Objets Métier {:?} enregistrée", module)))
+}
+
+/// Numéro final d'un ProgID versionné (`...Stream.1` -> 1)
+fn progid_revision(progid: &str) -> Option<u32> {
+    progid.rsplit('.').next().and_then(|s| s.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::com::MemoryRegistry;
+
+    const CPTA_V9: &str = "{309DE0FB-9FB8-4F4E-8295-CC60C60DAA33}";
+    const CPTA_V11: &str = "{0B3D7A9E-1111-4F4E-8295-CC60C60DAA33}";
+    const CIAL: &str = "{ED0EC116-16B8-44CC-A68A-41BF6E15EB3F}";
+
+    fn installation() -> MemoryRegistry {
+        MemoryRegistry::new()
+            .with_key(r"Objets100c.Cpta.Stream", Some("Version indépendante"))
+            .with_key(r"Objets100c.Cpta.Stream\CLSID", Some(CPTA_V9))
+            .with_key(r"Objets100c.Cpta.Stream.1\CLSID", Some(CPTA_V9))
+            .with_key(&format!(r"CLSID\{}\TypeLib", CPTA_V9), Some("{LIB-CPTA-9}"))
+            .with_key(&format!(r"CLSID\{}\InprocServer32", CPTA_V9), Some(r"C:\Sage\objets100c.dll"))
+            .with_key(r"TypeLib\{LIB-CPTA-9}\9.0", None)
+            .with_key(r"Objets100c.Cpta.Stream.2\CLSID", Some(CPTA_V11))
+            .with_key(&format!(r"CLSID\{}\TypeLib", CPTA_V11), Some("{LIB-CPTA-11}"))
+            .with_key(r"TypeLib\{LIB-CPTA-11}\a.0", None)
+            .with_key(r"TypeLib\{LIB-CPTA-11}\b.0", None)
+            .with_key(r"Objets100c.Cial.Stream.1\CLSID", Some(CIAL))
+            .with_key(r"Excel.Application\CLSID", Some("{00024500-0000-0000-C000-000000000046}"))
+    }
+
+    #[test]
+    fn test_discovery_orders_by_version() {
+        let classes = discover_classes(&installation()).unwrap();
+
+        let progids: Vec<&str> = classes.iter().map(|c| c.progid.as_str()).collect();
+        assert_eq!(progids, vec!["Objets100c.Cpta.Stream.2", "Objets100c.Cpta.Stream.1", "Objets100c.Cial.Stream.1"]);
+        assert_eq!(classes[0].version, Some(SageVersion { major: 11, minor: 0 }));
+        assert_eq!(classes[1].server.as_deref(), Some(r"C:\Sage\objets100c.dll"));
+        assert_eq!(classes[1].typelib.as_deref(), Some("{LIB-CPTA-9}"));
+        assert_eq!(classes[2].version, None);
+    }
+
+    #[test]
+    fn test_newest_class_per_module() {
+        let registry = installation();
+        assert_eq!(newest_class(&registry, SageModule::Cpta).unwrap().clsid, CPTA_V11);
+        assert_eq!(newest_class(&registry, SageModule::Cial).unwrap().clsid, CIAL);
+
+        let error = newest_class(&MemoryRegistry::new(), SageModule::Cial).unwrap_err();
+        assert!(error.is_class_not_registered());
+    }
+}
@@ -0,0 +1,151 @@
+use crate::errors::SageResult;
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+lazy_static! {
+    /// Cache partagé par tous les `SafeDispatch` du processus
+    static ref GLOBAL_CACHE: DispidCache = DispidCache::new();
+}
+
+/// Statistiques d'utilisation du cache de DISPID
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Résolutions servies par le cache
+    pub hits: u64,
+    /// Résolutions ayant nécessité un GetIDsOfNames
+    pub misses: u64,
+    /// Nombre de couples (interface, nom) mémorisés
+    pub entries: usize,
+}
+
+impl CacheStats {
+    /// Part des résolutions servies par le cache (0.0 à 1.0)
+    pub fn hit_ratio(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+#[derive(Default)]
+struct CacheState {
+    /// (GUID d'interface, nom en minuscules) -> DISPID
+    ids: HashMap<(String, String), i32>,
+    hits: u64,
+    misses: u64,
+}
+
+/// Cache nom -> DISPID indexé par le GUID de l'interface
+///
+/// Les DISPID sont fixés par la bibliothèque de types : tous les objets d'une
+/// même interface (ex: chaque IBOTiers3 d'une lecture en masse) partagent donc
+/// les mêmes entrées. Les noms sont insensibles à la casse comme avec IDispatch.
+pub struct DispidCache {
+    state: Mutex<CacheState>,
+}
+
+impl DispidCache {
+    /// Crée un cache vide
+    pub fn new() -> Self {
+        DispidCache {
+            state: Mutex::new(CacheState::default()),
+        }
+    }
+
+    /// Cache global utilisé par `SafeDispatch`
+    pub fn global() -> &'static DispidCache {
+        &GLOBAL_CACHE
+    }
+
+    /// Retourne le DISPID mémorisé, ou le résout avec `resolve` et le mémorise
+    pub fn get_or_resolve<F>(&self, interface_id: &str, name: &str, resolve: F) -> SageResult<i32>
+    where
+        F: FnOnce() -> SageResult<i32>,
+    {
+        let key = (interface_id.to_string(), name.to_lowercase());
+        {
+            let mut state = self.lock();
+            if let Some(dispid) = state.ids.get(&key).copied() {
+                state.hits += 1;
+                return Ok(dispid);
+            }
+            state.misses += 1;
+        }
+
+        // Résolution hors verrou : GetIDsOfNames peut être long (appel COM)
+        let dispid = resolve()?;
+        self.lock().ids.insert(key, dispid);
+        Ok(dispid)
+    }
+
+    /// DISPID mémorisé, sans modifier les statistiques
+    pub fn peek(&self, interface_id: &str, name: &str) -> Option<i32> {
+        self.lock()
+            .ids
+            .get(&(interface_id.to_string(), name.to_lowercase()))
+            .copied()
+    }
+
+    /// Statistiques courantes
+    pub fn stats(&self) -> CacheStats {
+        let state = self.lock();
+        CacheStats {
+            hits: state.hits,
+            misses: state.misses,
+            entries: state.ids.len(),
+        }
+    }
+
+    /// Vide le cache et remet les statistiques à zéro
+    pub fn clear(&self) {
+        *self.lock() = CacheState::default();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheState> {
+        // Un panic pendant une résolution ne rend pas le cache incohérent
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for DispidCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::SageError;
+
+    #[test]
+    fn test_cache_hits_and_case_insensitivity() {
+        let cache = DispidCache::new();
+        let iid = "{A1B2C3D4-0000-0000-0000-000000000001}";
+
+        assert_eq!(cache.get_or_resolve(iid, "CT_Num", || Ok(7)).unwrap(), 7);
+        assert_eq!(cache.get_or_resolve(iid, "ct_num", || panic!("déjà en cache")).unwrap(), 7);
+        assert!(cache.peek("{autre}", "CT_Num").is_none());
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 1, 1));
+        assert_eq!(stats.hit_ratio(), 0.5);
+    }
+
+    #[test]
+    fn test_failed_resolution_is_not_cached() {
+        let cache = DispidCache::new();
+        let result = cache.get_or_resolve("iid", "Inconnu", || {
+            Err(SageError::method_call("Inconnu", -1, "nom inconnu"))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(cache.stats().entries, 0);
+        cache.clear();
+        assert_eq!(cache.stats(), CacheStats::default());
+    }
+}
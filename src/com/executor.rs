@@ -0,0 +1,391 @@
+use super::ComApartment;
+use crate::errors::{SageError, SageResult};
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread::{self, JoinHandle};
+
+#[cfg(all(windows, feature = "com"))]
+use windows::Win32::{
+    Foundation::{LPARAM, WPARAM},
+    System::Threading::GetCurrentThreadId,
+    UI::WindowsAndMessaging::{
+        DispatchMessageW, GetMessageW, PeekMessageW, PostThreadMessageW, TranslateMessage, MSG, PM_NOREMOVE,
+        WM_APP, WM_QUIT,
+    },
+};
+
+/// Travail exécuté sur le thread STA avec l'état qu'il possède
+type Job<T> = Box<dyn FnOnce(&mut T) + Send>;
+
+/// Message signalant au thread STA qu'un travail est en attente
+#[cfg(all(windows, feature = "com"))]
+const WM_SAGE_JOB: u32 = WM_APP + 0x5A;
+
+/// Thread STA dédié aux appels Sage, pilotable depuis n'importe quel thread
+///
+/// Les objets COM créés en `COINIT_APARTMENTTHREADED` ne peuvent être utilisés
+/// que depuis le thread qui les a créés. L'exécuteur crée l'application sur
+/// son propre thread (qui pompe les messages Windows) et y exécute les
+/// closures qu'on lui confie ; seul le résultat traverse les threads.
+///
+/// ```ignore
+/// let executor = SageExecutor::spawn(|| {
+///     let mut app = CptaApplication::newest_installed()?;
+///     app.set_name(r"C:\Sage\BIJOU.MAE")?;
+///     app.open()?;
+///     Ok(app)
+/// })?;
+///
+/// // Depuis une tâche tokio
+/// let ouverte = executor.run(|app| app.is_open()).await?;
+/// ```
+pub struct SageExecutor<T: 'static> {
+    sender: Option<Sender<Job<T>>>,
+    thread: Option<JoinHandle<()>>,
+    #[cfg(all(windows, feature = "com"))]
+    thread_id: u32,
+}
+
+impl<T: 'static> SageExecutor<T> {
+    /// Démarre le thread STA et y construit l'état avec `init`
+    ///
+    /// L'erreur de `init` (ouverture de la base...) est renvoyée à l'appelant.
+    pub fn spawn<F>(init: F) -> SageResult<Self>
+    where
+        F: FnOnce() -> SageResult<T> + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel::<Job<T>>();
+        let (ready_tx, ready_rx) = mpsc::channel::<SageResult<u32>>();
+
+        let thread = thread::Builder::new()
+            .name("sage-sta".to_string())
+            .spawn(move || sta_main(init, receiver, ready_tx))
+            .map_err(|e| SageError::InternalError(format!("Démarrage du thread STA impossible: {}", e)))?;
+
+        let ready = ready_rx
+            .recv()
+            .unwrap_or_else(|_| Err(SageError::InternalError("Le thread STA s'est arrêté au démarrage".to_string())));
+        #[cfg_attr(not(all(windows, feature = "com")), allow(unused_variables))]
+        let thread_id = match ready {
+            Ok(thread_id) => thread_id,
+            Err(error) => {
+                let _ = thread.join();
+                return Err(error);
+            }
+        };
+
+        Ok(SageExecutor {
+            sender: Some(sender),
+            thread: Some(thread),
+            #[cfg(all(windows, feature = "com"))]
+            thread_id,
+        })
+    }
+
+    /// Exécute `job` sur le thread STA ; le résultat est attendu avec `.await`
+    ///
+    /// Le futur ne dépend d'aucun runtime (tokio, async-std...). Une panique
+    /// dans `job` est renvoyée comme `SageError::InternalError`.
+    pub fn run<F, R>(&self, job: F) -> SageFuture<R>
+    where
+        F: FnOnce(&mut T) -> SageResult<R> + Send + 'static,
+        R: Send + 'static,
+    {
+        let (completer, future) = SageFuture::pair();
+        let job: Job<T> = Box::new(move |state: &mut T| {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| job(state))).unwrap_or_else(|_| {
+                Err(SageError::InternalError("Panique pendant un appel sur le thread STA".to_string()))
+            });
+            completer.complete(result);
+        });
+
+        // En cas d'échec, le travail est abandonné et le futur se termine en erreur
+        if let Some(sender) = &self.sender
+            && sender.send(job).is_ok()
+        {
+            self.wake();
+        }
+        future
+    }
+
+    /// Comme `run`, en bloquant le thread appelant jusqu'au résultat
+    pub fn run_blocking<F, R>(&self, job: F) -> SageResult<R>
+    where
+        F: FnOnce(&mut T) -> SageResult<R> + Send + 'static,
+        R: Send + 'static,
+    {
+        if self.is_sta_thread() {
+            return Err(SageError::UnsupportedOperation(
+                "run_blocking appelé depuis le thread STA lui-même".to_string(),
+            ));
+        }
+        self.run(job).wait()
+    }
+
+    fn is_sta_thread(&self) -> bool {
+        self.thread.as_ref().map(|t| t.thread().id()) == Some(thread::current().id())
+    }
+
+    /// Réveille la boucle de messages du thread STA
+    #[cfg(all(windows, feature = "com"))]
+    fn wake(&self) {
+        unsafe {
+            let _ = PostThreadMessageW(self.thread_id, WM_SAGE_JOB, WPARAM(0), LPARAM(0));
+        }
+    }
+
+    #[cfg(not(all(windows, feature = "com")))]
+    fn wake(&self) {}
+}
+
+impl<T: 'static> Drop for SageExecutor<T> {
+    /// Termine les travaux en attente, libère l'état puis arrête le thread
+    fn drop(&mut self) {
+        self.sender.take();
+        #[cfg(all(windows, feature = "com"))]
+        unsafe {
+            let _ = PostThreadMessageW(self.thread_id, WM_QUIT, WPARAM(0), LPARAM(0));
+        }
+        if let Some(thread) = self.thread.take()
+            && thread.thread().id() != thread::current().id()
+        {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Corps du thread STA : initialisation, boucle de travail, libération
+fn sta_main<T, F>(init: F, receiver: Receiver<Job<T>>, ready: Sender<SageResult<u32>>)
+where
+    F: FnOnce() -> SageResult<T>,
+{
+    let apartment = match ComApartment::sta() {
+        Ok(apartment) => apartment,
+        Err(error) => {
+            let _ = ready.send(Err(error));
+            return;
+        }
+    };
+
+    #[cfg(all(windows, feature = "com"))]
+    let thread_id = unsafe {
+        // Crée la file de messages avant d'accepter des PostThreadMessage
+        let mut msg = MSG::default();
+        let _ = PeekMessageW(&mut msg, None, 0, 0, PM_NOREMOVE);
+        GetCurrentThreadId()
+    };
+    #[cfg(not(all(windows, feature = "com")))]
+    let thread_id = 0;
+
+    match panic::catch_unwind(AssertUnwindSafe(init)) {
+        Ok(Ok(mut state)) => {
+            let _ = ready.send(Ok(thread_id));
+            pump(&receiver, &mut state);
+            // L'état (objets COM) est libéré sur son thread, avant l'apartment
+            drop(state);
+        }
+        Ok(Err(error)) => {
+            let _ = ready.send(Err(error));
+        }
+        Err(_) => {
+            let _ = ready.send(Err(SageError::InternalError(
+                "Panique pendant l'initialisation du thread STA".to_string(),
+            )));
+        }
+    }
+
+    drop(apartment);
+}
+
+/// Boucle de messages : exécute les travaux et distribue les messages
+/// Windows dont dépendent les appels COM inter-apartments
+#[cfg(all(windows, feature = "com"))]
+fn pump<T>(receiver: &Receiver<Job<T>>, state: &mut T) {
+    let mut msg = MSG::default();
+    loop {
+        loop {
+            match receiver.try_recv() {
+                Ok(job) => job(state),
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => return,
+            }
+        }
+        // 0 : WM_QUIT, -1 : erreur ; les travaux déjà reçus sont terminés
+        if unsafe { GetMessageW(&mut msg, None, 0, 0) }.0 <= 0 {
+            while let Ok(job) = receiver.try_recv() {
+                job(state);
+            }
+            return;
+        }
+        if msg.message != WM_SAGE_JOB {
+            unsafe {
+                TranslateMessage(&msg);
+                DispatchMessageW(&msg);
+            }
+        }
+    }
+}
+
+/// Sans COM, aucun message à pomper : les travaux sont exécutés dans l'ordre
+#[cfg(not(all(windows, feature = "com")))]
+fn pump<T>(receiver: &Receiver<Job<T>>, state: &mut T) {
+    for job in receiver {
+        job(state);
+    }
+}
+
+struct Shared<R> {
+    result: Option<SageResult<R>>,
+    waker: Option<Waker>,
+}
+
+/// Résultat d'un travail confié à `SageExecutor::run`
+pub struct SageFuture<R> {
+    shared: Arc<(Mutex<Shared<R>>, Condvar)>,
+}
+
+/// Côté thread STA : fournit le résultat, ou une erreur s'il est abandonné
+struct Completer<R> {
+    shared: Option<Arc<(Mutex<Shared<R>>, Condvar)>>,
+}
+
+impl<R> SageFuture<R> {
+    fn pair() -> (Completer<R>, Self) {
+        let shared = Arc::new((Mutex::new(Shared { result: None, waker: None }), Condvar::new()));
+        (Completer { shared: Some(shared.clone()) }, SageFuture { shared })
+    }
+
+    /// Attend le résultat en bloquant le thread courant
+    pub fn wait(self) -> SageResult<R> {
+        let (lock, ready) = &*self.shared;
+        let mut shared = lock.lock().unwrap_or_else(|e| e.into_inner());
+        loop {
+            if let Some(result) = shared.result.take() {
+                return result;
+            }
+            shared = ready.wait(shared).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+}
+
+impl<R> Future for SageFuture<R> {
+    type Output = SageResult<R>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut shared = self.shared.0.lock().unwrap_or_else(|e| e.into_inner());
+        match shared.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<R> Completer<R> {
+    fn complete(mut self, result: SageResult<R>) {
+        if let Some(shared) = self.shared.take() {
+            Self::deliver(&shared, result);
+        }
+    }
+
+    fn deliver(shared: &(Mutex<Shared<R>>, Condvar), result: SageResult<R>) {
+        let (lock, ready) = shared;
+        let waker = {
+            let mut shared = lock.lock().unwrap_or_else(|e| e.into_inner());
+            shared.result = Some(result);
+            shared.waker.take()
+        };
+        ready.notify_all();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<R> Drop for Completer<R> {
+    fn drop(&mut self) {
+        if let Some(shared) = self.shared.take() {
+            Self::deliver(
+                &shared,
+                Err(SageError::InternalError("Le thread STA s'est arrêté avant l'appel".to_string())),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::com::FakeObject;
+    use crate::wrappers::CptaApplication;
+    use std::task::Wake;
+
+    /// Exécuteur minimal : endort le thread jusqu'au réveil du futur
+    struct ThreadWaker(thread::Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = std::pin::pin!(future);
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    fn application() -> SageResult<CptaApplication<FakeObject>> {
+        Ok(CptaApplication::from_backend(
+            FakeObject::new("IBSCPTAApplication3").with_property("IsOpen", true),
+        ))
+    }
+
+    #[test]
+    fn test_calls_run_on_owning_thread() {
+        let executor = SageExecutor::spawn(application).unwrap();
+        let caller = thread::current().id();
+
+        let (open, thread_id) = block_on(executor.run(|app| Ok((app.is_open()?, thread::current().id())))).unwrap();
+        assert!(open);
+        assert_ne!(thread_id, caller);
+        let model = executor.run_blocking(|_| Ok(ComApartment::current())).unwrap();
+        assert_eq!(model, Some(crate::com::ApartmentModel::SingleThreaded));
+
+        // L'exécuteur est partageable entre threads, l'application jamais
+        let executor = Arc::new(executor);
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let executor = executor.clone();
+                thread::spawn(move || executor.run_blocking(move |_| Ok(thread::current().id())).unwrap())
+            })
+            .collect();
+        assert!(handles.into_iter().all(|h| h.join().unwrap() == thread_id));
+    }
+
+    #[test]
+    fn test_errors_and_panics_are_reported() {
+        let failed = SageExecutor::<FakeObject>::spawn(|| Err(SageError::database_not_open()));
+        assert!(matches!(failed, Err(SageError::DatabaseNotOpen { .. })));
+
+        let executor = SageExecutor::spawn(|| Ok(FakeObject::new("IBOJournal3"))).unwrap();
+        let panicked = executor.run_blocking(|_| -> SageResult<()> { panic!("erreur de programmation") });
+        assert!(matches!(panicked, Err(SageError::InternalError(_))));
+
+        // Le thread survit à la panique
+        let type_name = executor.run_blocking(|fake| Ok(fake.type_name()));
+        assert_eq!(type_name.unwrap(), "IBOJournal3");
+    }
+}
@@ -0,0 +1,387 @@
+use super::backend::{DispatchBackend, DispatchObject, InvokeKind, VariantEnumerator};
+use super::SafeVariant;
+use crate::errors::{HResult, SageError, SageResult};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Implémentation d'une méthode factice
+type FakeMethod = Rc<dyn Fn(&[SafeVariant]) -> SageResult<SafeVariant>>;
+
+/// Trace d'un appel reçu par un `FakeObject`
+#[derive(Debug, Clone)]
+pub struct FakeCall {
+    pub name: String,
+    pub kind: InvokeKind,
+    pub params: Vec<SafeVariant>,
+}
+
+struct FakeState {
+    type_name: String,
+    interface_id: Option<String>,
+    /// Éléments énumérés par `_NewEnum` si l'objet simule une collection
+    items: Option<Vec<SafeVariant>>,
+    /// Noms des membres, l'index + 1 donne le DISPID
+    members: Vec<String>,
+    properties: HashMap<String, SafeVariant>,
+    methods: HashMap<String, FakeMethod>,
+    /// Noms des paramètres par méthode, l'index donne le DISPID du paramètre
+    param_names: HashMap<String, Vec<String>>,
+    calls: Vec<FakeCall>,
+}
+
+/// Objet d'automation en mémoire, scriptable, pour tester les wrappers sans Sage
+///
+/// Les noms de membres sont insensibles à la casse comme avec IDispatch.
+/// Les clones partagent le même état, ce qui permet d'inspecter les appels
+/// reçus après les avoir transmis à un wrapper.
+#[derive(Clone)]
+pub struct FakeObject {
+    state: Rc<RefCell<FakeState>>,
+}
+
+impl FakeObject {
+    /// Crée un objet factice vide (ex: "IBILoggable")
+    pub fn new(type_name: &str) -> Self {
+        FakeObject {
+            state: Rc::new(RefCell::new(FakeState {
+                type_name: type_name.to_string(),
+                interface_id: None,
+                items: None,
+                members: Vec::new(),
+                properties: HashMap::new(),
+                methods: HashMap::new(),
+                param_names: HashMap::new(),
+                calls: Vec::new(),
+            })),
+        }
+    }
+
+    /// Déclare une propriété avec sa valeur initiale
+    pub fn with_property<V: Into<SafeVariant>>(self, name: &str, value: V) -> Self {
+        {
+            let mut state = self.state.borrow_mut();
+            let key = state.register(name);
+            state.properties.insert(key, value.into());
+        }
+        self
+    }
+
+    /// Déclare une méthode dont le résultat est calculé par `method`
+    pub fn with_method<F>(self, name: &str, method: F) -> Self
+    where
+        F: Fn(&[SafeVariant]) -> SageResult<SafeVariant> + 'static,
+    {
+        {
+            let mut state = self.state.borrow_mut();
+            let key = state.register(name);
+            state.methods.insert(key, Rc::new(method));
+        }
+        self
+    }
+
+    /// Déclare une méthode dont les paramètres peuvent être passés par nom
+    ///
+    /// Les arguments nommés sont replacés à leur position, les positions non
+    /// fournies reçoivent `SafeVariant::Missing`.
+    pub fn with_method_params<F>(self, name: &str, param_names: &[&str], method: F) -> Self
+    where
+        F: Fn(&[SafeVariant]) -> SageResult<SafeVariant> + 'static,
+    {
+        {
+            let mut state = self.state.borrow_mut();
+            let key = name.to_lowercase();
+            state.param_names.insert(
+                key,
+                param_names.iter().map(|p| p.to_lowercase()).collect(),
+            );
+        }
+        self.with_method(name, method)
+    }
+
+    /// Simule une collection Sage : `Count`, `Item(i)` (base 1) et `_NewEnum`
+    pub fn with_items(self, items: Vec<SafeVariant>) -> Self {
+        let count = items.len() as i32;
+        let by_index = items.clone();
+        self.state.borrow_mut().items = Some(items);
+        self.with_property("Count", count).with_method("Item", move |params| {
+            let index = params
+                .first()
+                .ok_or_else(|| SageError::method_call("Item", -1, "Nombre de paramètres non valide"))?
+                .to_i32()?;
+            usize::try_from(index - 1)
+                .ok()
+                .and_then(|i| by_index.get(i).cloned())
+                .ok_or_else(|| SageError::method_call("Item", -1, "Index hors limites"))
+        })
+    }
+
+    /// Simule le GUID d'interface, ce qui active le cache de DISPID
+    pub fn with_interface_id(self, interface_id: &str) -> Self {
+        self.state.borrow_mut().interface_id = Some(interface_id.to_string());
+        self
+    }
+
+    /// Nom du type simulé
+    pub fn type_name(&self) -> String {
+        self.state.borrow().type_name.clone()
+    }
+
+    /// Valeur courante d'une propriété
+    pub fn property(&self, name: &str) -> Option<SafeVariant> {
+        self.state
+            .borrow()
+            .properties
+            .get(&name.to_lowercase())
+            .cloned()
+    }
+
+    /// Appels reçus depuis la création de l'objet
+    pub fn calls(&self) -> Vec<FakeCall> {
+        self.state.borrow().calls.clone()
+    }
+
+    fn unknown_member(&self, name: &str, dispid: i32) -> SageError {
+        SageError::method_call(
+            name,
+            dispid,
+            &format!(
+                "Membre '{}' inconnu sur l'objet factice {}",
+                name,
+                self.type_name()
+            ),
+        )
+    }
+}
+
+impl FakeState {
+    /// Enregistre un membre et retourne sa clé normalisée
+    fn register(&mut self, name: &str) -> String {
+        let key = name.to_lowercase();
+        if !self.members.contains(&key) {
+            self.members.push(key.clone());
+        }
+        key
+    }
+}
+
+impl DispatchBackend for FakeObject {
+    fn get_ids_of_names(&self, names: &[&str]) -> SageResult<Vec<i32>> {
+        let state = self.state.borrow();
+        let (member, param_names) = names.split_first().ok_or_else(|| {
+            SageError::invalid_parameter("names", "au moins un nom", "aucun nom")
+        })?;
+
+        // Les noms suivants sont des paramètres du membre, comme avec GetIDsOfNames
+        let params = state.param_names.get(&member.to_lowercase());
+        let param_ids = param_names.iter().map(|param| {
+            let key = param.to_lowercase();
+            params
+                .and_then(|names| names.iter().position(|name| *name == key))
+                .map(|index| index as i32)
+                .ok_or_else(|| {
+                    SageError::method_call(
+                        member,
+                        -1,
+                        &format!("Paramètre '{}' inconnu pour '{}'", param, member),
+                    )
+                })
+        });
+
+        std::iter::once(member)
+            .map(|name| {
+                let key = name.to_lowercase();
+                state
+                    .members
+                    .iter()
+                    .position(|member| *member == key)
+                    .map(|index| index as i32 + 1)
+                    .ok_or_else(|| {
+                        SageError::from_code(
+                            HResult::DISP_E_UNKNOWNNAME,
+                            &format!("Méthode '{}' non trouvée: nom inconnu", name),
+                        )
+                    })
+            })
+            .chain(param_ids)
+            .collect()
+    }
+
+    fn interface_id(&self) -> Option<String> {
+        self.state.borrow().interface_id.clone()
+    }
+
+    fn new_enum(&self) -> SageResult<Box<dyn VariantEnumerator>> {
+        let items = self.state.borrow().items.clone().ok_or_else(|| {
+            SageError::UnsupportedOperation(format!(
+                "L'objet factice {} n'est pas une collection",
+                self.type_name()
+            ))
+        })?;
+        Ok(Box::new(FakeEnumerator {
+            items: items.into_iter(),
+        }))
+    }
+
+    fn invoke(
+        &self,
+        dispid: i32,
+        name: &str,
+        kind: InvokeKind,
+        params: &[SafeVariant],
+    ) -> SageResult<SafeVariant> {
+        let (key, method) = {
+            let mut state = self.state.borrow_mut();
+            let key = usize::try_from(dispid - 1)
+                .ok()
+                .and_then(|index| state.members.get(index).cloned());
+            state.calls.push(FakeCall {
+                name: name.to_string(),
+                kind,
+                params: params.to_vec(),
+            });
+            let method = key.as_ref().and_then(|k| state.methods.get(k).cloned());
+            (key, method)
+        };
+
+        let key = key.ok_or_else(|| self.unknown_member(name, dispid))?;
+
+        match kind {
+            InvokeKind::Method | InvokeKind::PropertyGet => {
+                // L'appel de la closure se fait sans emprunt actif sur l'état
+                if let Some(method) = method {
+                    return method(params);
+                }
+                match self.state.borrow().properties.get(&key) {
+                    Some(value) if params.is_empty() => Ok(value.clone()),
+                    Some(_) => Err(SageError::method_call(
+                        name,
+                        dispid,
+                        "Nombre de paramètres non valide",
+                    )),
+                    None => Err(self.unknown_member(name, dispid)),
+                }
+            }
+            InvokeKind::PropertyPut | InvokeKind::PropertyPutRef => {
+                if params.len() != 1 {
+                    return Err(SageError::method_call(
+                        name,
+                        dispid,
+                        "Nombre de paramètres non valide",
+                    ));
+                }
+                let mut state = self.state.borrow_mut();
+                if !state.properties.contains_key(&key) {
+                    return Err(SageError::method_call(
+                        name,
+                        dispid,
+                        "Propriété en lecture seule",
+                    ));
+                }
+                state.properties.insert(key, params[0].clone());
+                Ok(SafeVariant::Empty)
+            }
+        }
+    }
+
+    fn invoke_named(
+        &self,
+        dispid: i32,
+        name: &str,
+        kind: InvokeKind,
+        params: &[SafeVariant],
+        named: &[(i32, SafeVariant)],
+    ) -> SageResult<SafeVariant> {
+        // Replacer les arguments nommés à leur position dans la signature
+        let mut merged = params.to_vec();
+        for (param_id, value) in named {
+            let index = usize::try_from(*param_id).map_err(|_| {
+                SageError::method_call(name, dispid, "DISPID de paramètre non valide")
+            })?;
+            if index < params.len() {
+                return Err(SageError::method_call(
+                    name,
+                    dispid,
+                    "Paramètre fourni à la fois par position et par nom",
+                ));
+            }
+            if merged.len() <= index {
+                merged.resize(index + 1, SafeVariant::Missing);
+            }
+            merged[index] = value.clone();
+        }
+
+        self.invoke(dispid, name, kind, &merged)
+    }
+}
+
+/// Énumérateur d'une collection factice
+struct FakeEnumerator {
+    items: std::vec::IntoIter<SafeVariant>,
+}
+
+impl VariantEnumerator for FakeEnumerator {
+    fn next_batch(&mut self, max: usize) -> SageResult<Vec<SafeVariant>> {
+        Ok(self.items.by_ref().take(max).collect())
+    }
+}
+
+impl From<FakeObject> for SafeVariant {
+    fn from(object: FakeObject) -> Self {
+        SafeVariant::Dispatch(DispatchObject::new(object))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::com::SafeDispatch;
+
+    #[test]
+    fn test_fake_property_roundtrip() {
+        let fake = FakeObject::new("IBILoggable").with_property("UserName", "");
+        let dispatch = SafeDispatch::new(&fake);
+
+        dispatch
+            .set_property_by_name("username", SafeVariant::from("<Administrateur>"))
+            .unwrap();
+        let value = dispatch.call_method_by_name("UserName", &[]).unwrap();
+
+        assert_eq!(value.to_string().unwrap(), "<Administrateur>");
+        assert_eq!(fake.calls().len(), 2);
+    }
+
+    #[test]
+    fn test_fake_named_params() {
+        let fake = FakeObject::new("IBOCompteG3")
+            .with_method_params("Solde", &["Compte", "DateDebut", "DateFin"], |params| {
+                Ok(SafeVariant::I4(params.len() as i32))
+            });
+
+        let ids = fake.get_ids_of_names(&["Solde", "DateFin"]).unwrap();
+        assert_eq!(ids, vec![1, 2]);
+        assert!(fake.get_ids_of_names(&["Solde", "Journal"]).is_err());
+
+        let result = fake
+            .invoke_named(1, "Solde", InvokeKind::Method, &[SafeVariant::from("411000")], &[(2, SafeVariant::R8(1.0))])
+            .unwrap();
+        assert_eq!(result.to_i32().unwrap(), 3);
+        assert!(matches!(fake.calls()[0].params[1], SafeVariant::Missing));
+    }
+
+    #[test]
+    fn test_fake_method_and_unknown_member() {
+        let fake = FakeObject::new("IBOJournalFactory3")
+            .with_method("ExistNumero", |params| {
+                Ok(SafeVariant::Bool(params[0].to_string()? == "VTE"))
+            });
+        let dispatch = SafeDispatch::new(&fake);
+
+        let exists = dispatch
+            .call_method_by_name("ExistNumero", &[SafeVariant::from("VTE")])
+            .unwrap();
+        assert!(exists.to_bool().unwrap());
+        let unknown = dispatch.call_method_by_name("ReadNumero", &[]).unwrap_err();
+        assert!(matches!(unknown.root(), SageError::ComError { hresult: HResult::DISP_E_UNKNOWNNAME, .. }));
+    }
+}
@@ -0,0 +1,314 @@
+use super::backend::{DispatchBackend, InvokeKind, VariantEnumerator};
+use super::{ComInstance, OutParam, OwnedExcepInfo, OwnedVariant, SafeVariant};
+use crate::errors::{AutomationError, ErrorSource, HResult, SageError, SageResult};
+use windows::{Win32::System::Com::*, Win32::System::Ole::*, Win32::System::Variant::*, core::*};
+
+/// GUID de l'interface décrite par l'ITypeInfo de l'objet, s'il en expose un
+pub(crate) fn type_interface_id(dispatch: &IDispatch) -> Option<String> {
+    unsafe {
+        if dispatch.GetTypeInfoCount().ok()? == 0 {
+            return None;
+        }
+        let type_info = dispatch.GetTypeInfo(0, 0).ok()?;
+        let attr = type_info.GetTypeAttr().ok()?;
+        let guid = (*attr).guid;
+        type_info.ReleaseTypeAttr(attr);
+        Some(format!("{{{:?}}}", guid))
+    }
+}
+
+/// Implémentation COM du backend d'automation
+impl DispatchBackend for IDispatch {
+    fn get_ids_of_names(&self, names: &[&str]) -> SageResult<Vec<i32>> {
+        let names_wide: Vec<Vec<u16>> = names
+            .iter()
+            .map(|name| name.encode_utf16().chain(std::iter::once(0)).collect())
+            .collect();
+        let name_ptrs: Vec<PCWSTR> = names_wide
+            .iter()
+            .map(|name| PCWSTR(name.as_ptr()))
+            .collect();
+        let mut dispatch_ids = vec![0i32; names.len()];
+        let method_name = names.first().copied().unwrap_or_default();
+
+        unsafe {
+            self.GetIDsOfNames(
+                &GUID::zeroed(),
+                name_ptrs.as_ptr(),
+                name_ptrs.len() as u32,
+                0,
+                dispatch_ids.as_mut_ptr(),
+            )
+            .map_err(|e| {
+                let message = format!("Méthode '{}' non trouvée: {}", method_name, e.message().to_string_lossy());
+                SageError::from_code_with_source(HResult::from(e.code()), &message, Some(ErrorSource::new(e)))
+            })?;
+        }
+
+        Ok(dispatch_ids)
+    }
+
+    // Pas d'`interface_id` : sur un IDispatch nu, le GUID coûterait trois
+    // appels COM à chaque résolution. Les backends qui le mémorisent
+    // (`DispatchObject`, `ComInstance`) passent par `type_interface_id`.
+
+    fn new_enum(&self) -> SageResult<Box<dyn VariantEnumerator>> {
+        let mut result = OwnedVariant::new();
+        let mut excep_info = OwnedExcepInfo::new();
+        let dispparams = DISPPARAMS::default();
+
+        unsafe {
+            self.Invoke(
+                DISPID_NEWENUM,
+                &GUID::zeroed(),
+                0,
+                DISPATCH_METHOD | DISPATCH_PROPERTYGET,
+                &dispparams,
+                Some(result.as_mut_ptr()),
+                Some(excep_info.as_mut_ptr()),
+                None,
+            )
+            .map_err(|e| automation_error("_NewEnum", DISPID_NEWENUM, &e, excep_info, None))?;
+        }
+
+        // _NewEnum retourne un IUnknown (parfois un IDispatch) exposant IEnumVARIANT
+        let unknown: Option<IUnknown> = unsafe {
+            let raw = result.as_raw();
+            match raw.Anonymous.Anonymous.vt {
+                VT_UNKNOWN => (*raw.Anonymous.Anonymous.Anonymous.punkVal).clone(),
+                VT_DISPATCH => (*raw.Anonymous.Anonymous.Anonymous.pdispVal)
+                    .as_ref()
+                    .and_then(|d| d.cast().ok()),
+                _ => None,
+            }
+        };
+        let enumerator = unknown
+            .and_then(|u| u.cast::<IEnumVARIANT>().ok())
+            .ok_or_else(|| {
+                SageError::method_call("_NewEnum", DISPID_NEWENUM, "IEnumVARIANT non supporté")
+            })?;
+
+        Ok(Box::new(ComEnumerator { inner: enumerator }))
+    }
+
+    fn invoke(
+        &self,
+        dispid: i32,
+        name: &str,
+        kind: InvokeKind,
+        params: &[SafeVariant],
+    ) -> SageResult<SafeVariant> {
+        self.invoke_named(dispid, name, kind, params, &[])
+    }
+
+    fn invoke_named(
+        &self,
+        dispid: i32,
+        name: &str,
+        kind: InvokeKind,
+        params: &[SafeVariant],
+        named: &[(i32, SafeVariant)],
+    ) -> SageResult<SafeVariant> {
+        let is_put = matches!(kind, InvokeKind::PropertyPut | InvokeKind::PropertyPutRef);
+
+        // Ordre de rgvarg : arguments nommés d'abord (dans l'ordre de
+        // rgdispidNamedArgs), puis arguments positionnels de droite à gauche.
+        // Les PROPPUT exigent que la valeur soit l'argument nommé DISPID_PROPERTYPUT.
+        let mut named_ids: Vec<i32> = Vec::with_capacity(named.len() + 1);
+        let mut ordered: Vec<&SafeVariant> = Vec::with_capacity(params.len() + named.len());
+        let mut positional = params;
+        if is_put {
+            let (value, indexes) = params.split_last().ok_or_else(|| {
+                SageError::method_call(name, dispid, "Valeur de propriété manquante")
+            })?;
+            named_ids.push(DISPID_PROPERTYPUT);
+            ordered.push(value);
+            positional = indexes;
+        }
+        for (param_id, value) in named {
+            named_ids.push(*param_id);
+            ordered.push(value);
+        }
+        ordered.extend(positional.iter().rev());
+
+        // Tous les VARIANT (paramètres, résultat) et l'EXCEPINFO sont possédés
+        // et libérés à la sortie, y compris en cas d'erreur
+        let mut result = OwnedVariant::new();
+        let mut excep_info = OwnedExcepInfo::new();
+        let mut arg_err: u32 = 0;
+
+        // Convertir les paramètres SafeVariant en VARIANT
+        // Les paramètres [in, out] sont passés en VT_BYREF|VT_VARIANT vers
+        // un VARIANT alloué à part (adresse stable pendant l'appel)
+        let mut by_ref_values: Vec<(&OutParam, Box<OwnedVariant>)> = Vec::new();
+        let mut variant_params: Vec<OwnedVariant> = Vec::new();
+        for param in ordered {
+            match param {
+                SafeVariant::ByRef(slot) => {
+                    let mut value = Box::new(slot.value().to_variant()?);
+                    let mut by_ref = VARIANT::default();
+                    // SAFETY: VARIANT zéroé, la vue VARIANT_0_0 est valide
+                    let inner = unsafe { &mut *by_ref.Anonymous.Anonymous };
+                    inner.vt = VARENUM(VT_BYREF.0 | VT_VARIANT.0);
+                    inner.Anonymous.pvarVal = value.as_mut_ptr();
+                    // VariantClear ne libère pas la cible d'un VT_BYREF
+                    variant_params.push(unsafe { OwnedVariant::from_raw(by_ref) });
+                    by_ref_values.push((slot, value));
+                }
+                _ => variant_params.push(param.to_variant()?),
+            }
+        }
+
+        let dispparams = DISPPARAMS {
+            rgvarg: if variant_params.is_empty() {
+                std::ptr::null_mut()
+            } else {
+                variant_params.as_mut_ptr() as *mut VARIANT
+            },
+            rgdispidNamedArgs: if named_ids.is_empty() {
+                std::ptr::null_mut()
+            } else {
+                named_ids.as_mut_ptr()
+            },
+            cArgs: variant_params.len() as u32,
+            cNamedArgs: named_ids.len() as u32,
+        };
+
+        let flags = match kind {
+            InvokeKind::Method => DISPATCH_METHOD | DISPATCH_PROPERTYGET,
+            InvokeKind::PropertyGet => DISPATCH_PROPERTYGET,
+            InvokeKind::PropertyPut => DISPATCH_PROPERTYPUT,
+            InvokeKind::PropertyPutRef => DISPATCH_PROPERTYPUTREF,
+        };
+
+        let hr = unsafe {
+            self.Invoke(
+                dispid,
+                &GUID::zeroed(),
+                0,
+                flags,
+                &dispparams,
+                if is_put { None } else { Some(result.as_mut_ptr()) },
+                Some(excep_info.as_mut_ptr()),
+                Some(&mut arg_err),
+            )
+        };
+
+        // Relire les valeurs écrites par la méthode dans les paramètres [in, out]
+        if hr.is_ok() {
+            for (slot, value) in &by_ref_values {
+                slot.set(value.to_safe_variant()?);
+            }
+        }
+
+        match hr {
+            Ok(_) if is_put => Ok(SafeVariant::Empty),
+            Ok(_) => result.to_safe_variant(),
+            Err(e) => {
+                let hresult = HResult::from(e.code());
+                let arg_position =
+                    AutomationError::arg_position(hresult, arg_err, positional.len(), &named_ids);
+                Err(automation_error(name, dispid, &e, excep_info, arg_position))
+            }
+        }
+    }
+}
+
+/// Construit l'erreur d'un Invoke à partir de l'EXCEPINFO, classée via le catalogue Sage
+fn automation_error(
+    name: &str,
+    dispid: i32,
+    error: &Error,
+    mut excep_info: OwnedExcepInfo,
+    arg_position: Option<usize>,
+) -> SageError {
+    excep_info.fill_deferred();
+    let description = match excep_info.description() {
+        description if !description.is_empty() => description,
+        _ => format!("Erreur COM: {}", error.message().to_string_lossy()),
+    };
+    let help_file = Some(excep_info.help_file()).filter(|file| !file.is_empty());
+
+    SageError::from_automation(AutomationError {
+        member: name.to_string(),
+        dispid,
+        hresult: HResult::from(error.code()),
+        scode: HResult(excep_info.scode()),
+        code: excep_info.code(),
+        source: excep_info.source(),
+        description,
+        help_file,
+        help_context: excep_info.help_context(),
+        arg_position,
+    })
+}
+
+/// Énumérateur COM lisant les éléments par lots via `IEnumVARIANT::Next`
+struct ComEnumerator {
+    inner: IEnumVARIANT,
+}
+
+impl VariantEnumerator for ComEnumerator {
+    fn next_batch(&mut self, max: usize) -> SageResult<Vec<SafeVariant>> {
+        // Les VARIANT reçus sont possédés et libérés après conversion
+        let mut buffer: Vec<OwnedVariant> = (0..max.max(1)).map(|_| OwnedVariant::new()).collect();
+        let mut fetched: u32 = 0;
+
+        // SAFETY: OwnedVariant est #[repr(transparent)] sur VARIANT
+        let slots = unsafe {
+            std::slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut VARIANT, buffer.len())
+        };
+        // S_FALSE (moins d'éléments que demandés) n'est pas une erreur
+        unsafe { self.inner.Next(slots, &mut fetched) }
+            .ok()
+            .map_err(|e| {
+                SageError::method_call(
+                    "Next",
+                    -1,
+                    &format!("Erreur d'énumération: {}", e.message().to_string_lossy()),
+                )
+            })?;
+
+        buffer
+            .iter()
+            .take(fetched as usize)
+            .map(OwnedVariant::to_safe_variant)
+            .collect()
+    }
+}
+
+/// Une instance COM se comporte comme son interface IDispatch
+impl DispatchBackend for ComInstance {
+    fn get_ids_of_names(&self, names: &[&str]) -> SageResult<Vec<i32>> {
+        self.dispatch()?.get_ids_of_names(names)
+    }
+
+    fn interface_id(&self) -> Option<String> {
+        ComInstance::interface_id(self)
+    }
+
+    fn new_enum(&self) -> SageResult<Box<dyn VariantEnumerator>> {
+        self.dispatch()?.new_enum()
+    }
+
+    fn invoke(
+        &self,
+        dispid: i32,
+        name: &str,
+        kind: InvokeKind,
+        params: &[SafeVariant],
+    ) -> SageResult<SafeVariant> {
+        self.dispatch()?.invoke(dispid, name, kind, params)
+    }
+
+    fn invoke_named(
+        &self,
+        dispid: i32,
+        name: &str,
+        kind: InvokeKind,
+        params: &[SafeVariant],
+        named: &[(i32, SafeVariant)],
+    ) -> SageResult<SafeVariant> {
+        self.dispatch()?.invoke_named(dispid, name, kind, params, named)
+    }
+}
@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+
+/// Nature d'un membre d'interface d'automation (INVOKEKIND)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MemberType {
+    Method,
+    PropertyGet,
+    PropertyPut,
+    PropertyPutRef,
+}
+
+/// Paramètre d'un membre, tel que décrit par le FUNCDESC
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParamInfo {
+    pub name: String,
+    /// Type lisible (ex: "string", "IBOJournal3*", "array<variant>")
+    pub type_name: String,
+    /// Paramètre `[optional]` ou au-delà des paramètres obligatoires
+    pub optional: bool,
+    /// Valeur par défaut déclarée (`[defaultvalue(...)]`), convertie en texte
+    pub default_value: Option<String>,
+    /// Paramètre `[out]`, passé par référence
+    pub is_out: bool,
+}
+
+/// Description d'un membre découvert par introspection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemberInfo {
+    pub id: i32,
+    pub name: String,
+    pub member_type: MemberType,
+    pub param_count: Option<u32>,
+    pub return_type: Option<String>,
+    /// Paramètres dans l'ordre de la signature (hors `[retval]`)
+    pub params: Vec<ParamInfo>,
+    /// Chaîne d'aide du membre, si la bibliothèque de types en fournit une
+    pub description: Option<String>,
+}
+
+impl MemberInfo {
+    /// Signature lisible, par exemple `ReadNumero(sNum: string) -> IBOJournal3*`
+    pub fn signature(&self) -> String {
+        let params: Vec<String> = self
+            .params
+            .iter()
+            .map(|p| {
+                let mut param = format!("{}: {}", p.name, p.type_name);
+                if let Some(default) = &p.default_value {
+                    param.push_str(&format!(" = {}", default));
+                }
+                if p.optional {
+                    format!("[{}]", param)
+                } else {
+                    param
+                }
+            })
+            .collect();
+
+        match self.return_type.as_deref() {
+            Some(ret) if ret != "void" => format!("{}({}) -> {}", self.name, params.join(", "), ret),
+            _ => format!("{}({})", self.name, params.join(", ")),
+        }
+    }
+
+    /// Nombre de paramètres obligatoires
+    pub fn required_param_count(&self) -> usize {
+        self.params.iter().filter(|p| !p.optional).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_with_optional_params() {
+        let member = MemberInfo {
+            id: 12,
+            name: "Solde".to_string(),
+            member_type: MemberType::Method,
+            param_count: Some(2),
+            return_type: Some("currency".to_string()),
+            params: vec![
+                ParamInfo {
+                    name: "sCompte".to_string(),
+                    type_name: "string".to_string(),
+                    optional: false,
+                    default_value: None,
+                    is_out: false,
+                },
+                ParamInfo {
+                    name: "bCumul".to_string(),
+                    type_name: "bool".to_string(),
+                    optional: true,
+                    default_value: Some("false".to_string()),
+                    is_out: false,
+                },
+            ],
+            description: None,
+        };
+
+        assert_eq!(
+            member.signature(),
+            "Solde(sCompte: string, [bCumul: bool = false]) -> currency"
+        );
+        assert_eq!(member.required_param_count(), 1);
+    }
+}
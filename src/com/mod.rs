@@ -1,12 +1,59 @@
+#[cfg(all(windows, feature = "com"))]
 pub mod instance;
+pub mod apartment;
+pub mod backend;
+pub mod cassette;
+pub mod convert;
+pub mod currency;
+pub mod discovery;
 pub mod dispatch;
+pub mod dispid_cache;
+pub mod executor;
+pub mod fake;
+#[cfg(all(windows, feature = "com"))]
+mod idispatch;
+pub mod member;
+pub mod object;
+pub mod ole_date;
+pub mod out_param;
+#[cfg(all(windows, feature = "com"))]
+pub mod owned_variant;
+pub mod path;
+pub mod registry;
+pub mod safe_array;
 pub mod variant;
+#[cfg(all(windows, feature = "com"))]
+mod variant_com;
+mod variant_serde;
 pub mod safe_string;
+#[cfg(all(windows, feature = "com"))]
+pub mod type_info;
+pub mod typelib;
 
 
-pub use instance::{ComInstance, MemberInfo, MemberType};
+#[cfg(all(windows, feature = "com"))]
+pub use instance::ComInstance;
+pub use apartment::{ApartmentModel, ComApartment};
+pub use backend::{DetachedDispatch, DispatchBackend, DispatchObject, InvokeKind, VariantEnumerator};
+pub use cassette::{Cassette, CassetteEntry, Player, RecordedError, Recorder};
+pub use convert::{FromVariant, IntoVariant, SageRecord};
+pub use currency::RoundingMode;
+pub use discovery::{SageClassInfo, SageModule, SageVersion, discover_classes, newest_class};
 pub use dispatch::{SafeDispatch, FromDispatch};
+pub use dispid_cache::{CacheStats, DispidCache};
+pub use executor::{SageExecutor, SageFuture};
+pub use fake::{FakeCall, FakeObject};
+pub use member::{MemberInfo, MemberType, ParamInfo};
+pub use object::SageObject;
+pub use out_param::OutParam;
+#[cfg(all(windows, feature = "com"))]
+pub use owned_variant::{OwnedExcepInfo, OwnedVariant};
+pub use path::{PathSegment, PropertyPath};
+#[cfg(all(windows, feature = "com"))]
+pub use registry::WindowsRegistry;
+pub use registry::{MemoryRegistry, RegistryReader};
+pub use safe_array::{ArrayBound, ArrayData, SafeArray};
+pub use typelib::{ConstantInfo, EnumValue, FieldInfo, ImplementedInterface, TypeEntry, TypeKind, TypeLibrary};
 pub use variant::SafeVariant;
 #[allow(unused_imports)] // Sera utilisé dans les futures versions
 pub use safe_string::SafeString;
-
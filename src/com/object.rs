@@ -0,0 +1,235 @@
+use super::backend::{DispatchBackend, DispatchObject, InvokeKind, VariantEnumerator};
+use super::convert::{FromVariant, IntoVariant};
+use super::dispatch::{FromDispatch, SafeDispatch};
+use super::SafeVariant;
+use crate::errors::SageResult;
+use rust_decimal::Decimal;
+
+/// Objet Sage à liaison tardive, possédé et clonable
+///
+/// Alternative aux wrappers typés pour les interfaces qui n'en ont pas
+/// encore : chaque appel passe par le nom du membre, comme en VBScript.
+/// Le clone partage le même objet d'automation (AddRef).
+///
+/// ```ignore
+/// let journal = app.child("FactoryJournal")?.call_object("ReadNumero", &["VTE".into()])?;
+/// println!("{}", journal.as_str("JO_Intitule")?);
+/// journal.set("JO_Intitule", "Ventes France")?;
+/// journal.call("Write", &[])?;
+/// ```
+#[derive(Clone, Debug)]
+pub struct SageObject {
+    dispatch: DispatchObject,
+}
+
+impl SageObject {
+    /// Crée l'objet à partir d'un backend d'automation
+    pub fn new(dispatch: DispatchObject) -> Self {
+        SageObject { dispatch }
+    }
+
+    /// Construit l'objet à partir du résultat d'un appel (VT_DISPATCH)
+    pub fn from_variant(value: &SafeVariant) -> SageResult<Self> {
+        Ok(SageObject::new(value.to_object()?))
+    }
+
+    /// Objet d'automation sous-jacent
+    pub fn dispatch(&self) -> &DispatchObject {
+        &self.dispatch
+    }
+
+    /// Crée un SafeDispatch temporaire pour les appels
+    fn safe(&self) -> SafeDispatch<'_> {
+        SafeDispatch::new(&self.dispatch)
+    }
+
+    /// Lit une propriété - ÉQUIVALENT VB `objet.Nom`
+    pub fn get(&self, name: &str) -> SageResult<SafeVariant> {
+        self.safe().get_property_by_name(name)
+    }
+
+    /// Lit une propriété et la convertit dans le type demandé
+    pub fn get_as<T: FromVariant>(&self, name: &str) -> SageResult<T> {
+        T::from_variant(&self.get(name)?)
+    }
+
+    /// Écrit une propriété par valeur - ÉQUIVALENT VB `objet.Nom = valeur`
+    pub fn set<V: IntoVariant>(&self, name: &str, value: V) -> SageResult<()> {
+        self.safe().set_property_by_name(name, value.into_variant()?)
+    }
+
+    /// Affecte un objet par référence - ÉQUIVALENT VB `Set objet.Nom = autre`
+    pub fn set_ref(&self, name: &str, value: &SageObject) -> SageResult<()> {
+        self.safe()
+            .set_property_ref_by_name(name, SafeVariant::Dispatch(value.dispatch.clone()))
+    }
+
+    /// Appelle une méthode - ÉQUIVALENT VB `objet.Methode(params)`
+    pub fn call(&self, name: &str, params: &[SafeVariant]) -> SageResult<SafeVariant> {
+        self.safe().call_method_by_name(name, params)
+    }
+
+    /// Appelle une méthode renvoyant un objet (`ReadNumero`, `Create`...)
+    pub fn call_object(&self, name: &str, params: &[SafeVariant]) -> SageResult<SageObject> {
+        SageObject::from_variant(&self.call(name, params)?)
+    }
+
+    /// Objet enfant désigné par une propriété ou un chemin pointé
+    /// (`"FactoryJournal"`, `"Licence.Products(1)"`)
+    pub fn child(&self, path: &str) -> SageResult<SageObject> {
+        SageObject::from_variant(&self.safe().get_path(path)?)
+    }
+
+    /// Évalue un chemin pointé, voir `SafeDispatch::get_path`
+    pub fn get_path(&self, path: &str) -> SageResult<SafeVariant> {
+        self.safe().get_path(path)
+    }
+
+    /// Affecte la valeur désignée par un chemin pointé, voir `SafeDispatch::set_path`
+    pub fn set_path<V: IntoVariant>(&self, path: &str, value: V) -> SageResult<()> {
+        self.safe().set_path(path, value.into_variant()?)
+    }
+
+    /// Propriété entière (VT_I2, VT_I4...)
+    pub fn as_i32(&self, name: &str) -> SageResult<i32> {
+        self.get_as(name)
+    }
+
+    /// Propriété numérique en virgule flottante
+    pub fn as_f64(&self, name: &str) -> SageResult<f64> {
+        self.get_as(name)
+    }
+
+    /// Propriété booléenne (VARIANT_BOOL)
+    pub fn as_bool(&self, name: &str) -> SageResult<bool> {
+        self.get_as(name)
+    }
+
+    /// Propriété texte (BSTR), copiée dans une `String`
+    pub fn as_str(&self, name: &str) -> SageResult<String> {
+        self.get_as(name)
+    }
+
+    /// Montant exact (VT_CY, VT_DECIMAL)
+    pub fn as_decimal(&self, name: &str) -> SageResult<Decimal> {
+        self.get_as(name)
+    }
+}
+
+impl DispatchBackend for SageObject {
+    fn get_ids_of_names(&self, names: &[&str]) -> SageResult<Vec<i32>> {
+        self.dispatch.get_ids_of_names(names)
+    }
+
+    fn invoke(
+        &self,
+        dispid: i32,
+        name: &str,
+        kind: InvokeKind,
+        params: &[SafeVariant],
+    ) -> SageResult<SafeVariant> {
+        self.dispatch.invoke(dispid, name, kind, params)
+    }
+
+    fn interface_id(&self) -> Option<String> {
+        self.dispatch.interface_id()
+    }
+
+    fn new_enum(&self) -> SageResult<Box<dyn VariantEnumerator>> {
+        self.dispatch.new_enum()
+    }
+
+    fn invoke_named(
+        &self,
+        dispid: i32,
+        name: &str,
+        kind: InvokeKind,
+        params: &[SafeVariant],
+        named: &[(i32, SafeVariant)],
+    ) -> SageResult<SafeVariant> {
+        self.dispatch.invoke_named(dispid, name, kind, params, named)
+    }
+}
+
+impl FromDispatch<DispatchObject> for SageObject {
+    fn from_dispatch(dispatch: DispatchObject) -> SageResult<Self> {
+        Ok(SageObject::new(dispatch))
+    }
+}
+
+impl From<DispatchObject> for SageObject {
+    fn from(dispatch: DispatchObject) -> Self {
+        SageObject::new(dispatch)
+    }
+}
+
+impl FromVariant for SageObject {
+    fn from_variant(value: &SafeVariant) -> SageResult<Self> {
+        SageObject::from_variant(value)
+    }
+}
+
+impl IntoVariant for SageObject {
+    fn into_variant(self) -> SageResult<SafeVariant> {
+        Ok(SafeVariant::Dispatch(self.dispatch))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::com::FakeObject;
+
+    fn application() -> (SageObject, FakeObject) {
+        let journal = FakeObject::new("IBOJournal3")
+            .with_property("JO_Num", "VTE")
+            .with_property("JO_Intitule", "Ventes")
+            .with_property("JO_Sommeil", false)
+            .with_property("CompteG", SafeVariant::Null);
+        let factory = FakeObject::new("IBOJournalFactory3").with_method("ReadNumero", move |params| {
+            match params.first().map(|p| p.to_string()) {
+                Some(Ok(numero)) if numero == "VTE" => Ok(SafeVariant::from(journal.clone())),
+                _ => Ok(SafeVariant::Null),
+            }
+        });
+        let compte = FakeObject::new("IBOCompteG3").with_property("CG_Num", "701000");
+        let app = FakeObject::new("IBSCPTAApplication3").with_property("FactoryJournal", factory);
+        (SageObject::from_variant(&SafeVariant::from(app)).unwrap(), compte)
+    }
+
+    #[test]
+    fn test_owned_navigation() {
+        let (app, _) = application();
+
+        // L'objet renvoyé ne dépend plus d'aucun emprunt sur `app`
+        let journal = {
+            let factory = app.child("FactoryJournal").unwrap();
+            factory.call_object("ReadNumero", &["VTE".into()]).unwrap()
+        };
+        assert_eq!(journal.as_str("JO_Intitule").unwrap(), "Ventes");
+        assert!(!journal.as_bool("JO_Sommeil").unwrap());
+
+        // Une lecture de propriété passe par DISPATCH_PROPERTYGET
+        let fake = journal.dispatch().downcast_ref::<FakeObject>().unwrap();
+        assert_eq!(fake.calls().pop().unwrap().kind, InvokeKind::PropertyGet);
+
+        let alias = journal.clone();
+        alias.set("JO_Intitule", "Ventes France").unwrap();
+        assert_eq!(journal.get_as::<String>("JO_Intitule").unwrap(), "Ventes France");
+        assert!(app.child("FactoryJournal.ReadNumero(\"ACH\")").is_err());
+    }
+
+    #[test]
+    fn test_set_ref_passes_dispatch() {
+        let (app, compte) = application();
+        let journal = app.child("FactoryJournal.ReadNumero(\"VTE\")").unwrap();
+        let compte = SageObject::from_variant(&SafeVariant::from(compte)).unwrap();
+        journal.set_ref("CompteG", &compte).unwrap();
+
+        let fake = journal.dispatch().downcast_ref::<FakeObject>().unwrap();
+        let call = fake.calls().pop().unwrap();
+        assert_eq!(call.kind, InvokeKind::PropertyPutRef);
+        assert_eq!(journal.child("CompteG").unwrap().as_str("CG_Num").unwrap(), "701000");
+        assert!(journal.set_ref("CompteA", &compte).is_err());
+    }
+}
@@ -0,0 +1,104 @@
+use crate::errors::{SageError, SageResult};
+use chrono::{Duration, NaiveDate, NaiveDateTime, Timelike};
+
+/// Plus petite date OLE Automation acceptée (01/01/0100)
+pub const OLE_DATE_MIN: f64 = -657_434.0;
+/// Plus grande date OLE Automation acceptée, maximum de VT_DATE
+/// (31/12/9999 23:59:59.999 à la milliseconde près)
+pub const OLE_DATE_MAX: f64 = 2_958_465.999_999_99;
+
+const MILLIS_PER_DAY: f64 = 86_400_000.0;
+
+/// Origine des dates OLE : 30/12/1899 à minuit
+fn ole_epoch() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(1899, 12, 30)
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .expect("date d'origine OLE valide")
+}
+
+fn out_of_range(value: impl ToString) -> SageError {
+    SageError::ConversionError {
+        from_type: "Date".to_string(),
+        to_type: "NaiveDateTime".to_string(),
+        value: format!("{} hors de la plage des dates OLE", value.to_string()),
+    }
+}
+
+/// Convertit une date OLE Automation (VT_DATE) en date/heure chrono
+///
+/// La partie entière compte les jours depuis le 30/12/1899 et peut être
+/// négative ; la partie décimale est toujours l'heure du jour, même pour les
+/// dates négatives (-1.25 = 29/12/1899 06:00). L'heure est arrondie à la
+/// milliseconde pour absorber l'imprécision du f64.
+pub fn ole_date_to_naive_datetime(value: f64) -> SageResult<NaiveDateTime> {
+    if !value.is_finite() || !(OLE_DATE_MIN..=OLE_DATE_MAX).contains(&value) {
+        return Err(out_of_range(value));
+    }
+
+    let days = value.trunc();
+    let time_millis = ((value - days).abs() * MILLIS_PER_DAY).round() as i64;
+
+    ole_epoch()
+        .checked_add_signed(Duration::days(days as i64))
+        .and_then(|date| date.checked_add_signed(Duration::milliseconds(time_millis)))
+        .ok_or_else(|| out_of_range(value))
+}
+
+/// Convertit une date/heure chrono en date OLE Automation (VT_DATE)
+pub fn naive_datetime_to_ole_date(datetime: NaiveDateTime) -> SageResult<f64> {
+    let days = datetime.date().signed_duration_since(ole_epoch().date()).num_days() as f64;
+    let time = datetime.time();
+    let time_millis = time.num_seconds_from_midnight() as f64 * 1000.0
+        + (time.nanosecond() / 1_000_000) as f64;
+    let fraction = time_millis / MILLIS_PER_DAY;
+
+    // L'heure s'éloigne de zéro : 29/12/1899 06:00 = -1.25
+    let value = if days < 0.0 { days - fraction } else { days + fraction };
+
+    if (OLE_DATE_MIN..=OLE_DATE_MAX).contains(&value) {
+        Ok(value)
+    } else {
+        Err(out_of_range(datetime))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn datetime(y: i32, m: u32, d: u32, h: u32, min: u32, s: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(h, min, s)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_known_dates() {
+        assert_eq!(ole_date_to_naive_datetime(0.0).unwrap(), datetime(1899, 12, 30, 0, 0, 0));
+        assert_eq!(ole_date_to_naive_datetime(45123.5).unwrap(), datetime(2023, 7, 16, 12, 0, 0));
+        assert_eq!(naive_datetime_to_ole_date(datetime(2023, 7, 16, 12, 0, 0)).unwrap(), 45123.5);
+    }
+
+    #[test]
+    fn test_negative_dates_keep_positive_time() {
+        assert_eq!(ole_date_to_naive_datetime(-1.25).unwrap(), datetime(1899, 12, 29, 6, 0, 0));
+        assert_eq!(naive_datetime_to_ole_date(datetime(1899, 12, 29, 6, 0, 0)).unwrap(), -1.25);
+        assert_eq!(ole_date_to_naive_datetime(-2.0).unwrap(), datetime(1899, 12, 28, 0, 0, 0));
+    }
+
+    #[test]
+    fn test_fraction_rounding_and_range() {
+        // 1 seconde avant minuit, avec le bruit habituel du f64
+        let value = 45123.0 + 86_399.0 / 86_400.0 + 1e-10;
+        assert_eq!(ole_date_to_naive_datetime(value).unwrap(), datetime(2023, 7, 16, 23, 59, 59));
+
+        let last = NaiveDate::from_ymd_opt(9999, 12, 31).unwrap().and_hms_milli_opt(23, 59, 59, 999).unwrap();
+        let value = naive_datetime_to_ole_date(last).unwrap();
+        assert_eq!(ole_date_to_naive_datetime(value).unwrap(), last);
+        assert_eq!(ole_date_to_naive_datetime(OLE_DATE_MAX).unwrap(), last);
+
+        assert!(ole_date_to_naive_datetime(f64::NAN).is_err());
+        assert!(ole_date_to_naive_datetime(3_000_000.0).is_err());
+    }
+}
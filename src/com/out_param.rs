@@ -0,0 +1,71 @@
+use super::SafeVariant;
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+/// Emplacement d'un paramètre `[in, out]` passé par référence (VT_BYREF)
+///
+/// Le slot est partagé : on en passe une copie via `as_param()` dans la liste
+/// des paramètres, puis on lit la valeur écrite par la méthode après l'appel.
+///
+/// ```ignore
+/// let solde = OutParam::new(SafeVariant::R8(0.0));
+/// dispatch.call_method_by_name("CalculSolde", &[SafeVariant::from("411000"), solde.as_param()])?;
+/// println!("Solde: {}", solde.value().to_f64()?);
+/// ```
+#[derive(Clone)]
+pub struct OutParam {
+    value: Rc<RefCell<SafeVariant>>,
+}
+
+impl OutParam {
+    /// Crée un slot avec sa valeur d'entrée
+    pub fn new(initial: SafeVariant) -> Self {
+        OutParam {
+            value: Rc::new(RefCell::new(initial)),
+        }
+    }
+
+    /// Paramètre à placer dans la liste d'arguments de l'appel
+    pub fn as_param(&self) -> SafeVariant {
+        SafeVariant::ByRef(self.clone())
+    }
+
+    /// Valeur courante du slot (mise à jour après `Invoke`)
+    pub fn value(&self) -> SafeVariant {
+        self.value.borrow().clone()
+    }
+
+    /// Remplace la valeur du slot
+    pub fn set(&self, value: impl Into<SafeVariant>) {
+        *self.value.borrow_mut() = value.into();
+    }
+
+    /// Consomme le slot et retourne sa valeur
+    pub fn take(&self) -> SafeVariant {
+        self.value.replace(SafeVariant::Empty)
+    }
+}
+
+impl fmt::Debug for OutParam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("OutParam").field(&*self.value.borrow()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_out_param_shared_slot() {
+        let slot = OutParam::new(SafeVariant::I4(0));
+        let param = slot.as_param();
+
+        param.as_out_param().unwrap().set(42);
+
+        assert_eq!(slot.value().to_i32().unwrap(), 42);
+        assert_eq!(slot.take().to_i32().unwrap(), 42);
+        assert!(slot.value().is_empty_or_null());
+    }
+}
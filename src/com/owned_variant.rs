@@ -0,0 +1,161 @@
+use super::SafeVariant;
+use crate::errors::SageResult;
+use std::mem::ManuallyDrop;
+use windows::Win32::System::{Com::EXCEPINFO, Variant::*};
+
+/// VARIANT possédé, libéré par `VariantClear` à sa destruction
+///
+/// Libère les BSTR, SAFEARRAY et références d'interface que le VARIANT
+/// contient. `#[repr(transparent)]` permet de passer un `[OwnedVariant]`
+/// comme tableau `rgvarg` de DISPPARAMS.
+#[repr(transparent)]
+pub struct OwnedVariant(VARIANT);
+
+impl OwnedVariant {
+    /// Crée un VARIANT vide (VT_EMPTY)
+    pub fn new() -> Self {
+        OwnedVariant(VARIANT::default())
+    }
+
+    /// Prend possession d'un VARIANT
+    ///
+    /// # Safety
+    /// Le VARIANT doit être valide et ne plus être libéré par personne d'autre.
+    pub unsafe fn from_raw(variant: VARIANT) -> Self {
+        OwnedVariant(variant)
+    }
+
+    /// Rend la propriété du VARIANT à l'appelant, qui devra le libérer
+    pub fn into_raw(self) -> VARIANT {
+        let this = ManuallyDrop::new(self);
+        // SAFETY: `this` n'est plus détruit, le VARIANT est déplacé une seule fois
+        unsafe { std::ptr::read(&this.0) }
+    }
+
+    /// Accès en lecture au VARIANT
+    pub fn as_raw(&self) -> &VARIANT {
+        &self.0
+    }
+
+    /// Pointeur pour les API qui écrivent dans le VARIANT (résultat d'Invoke)
+    pub fn as_mut_ptr(&mut self) -> *mut VARIANT {
+        &mut self.0
+    }
+
+    /// Type du VARIANT
+    pub fn vt(&self) -> VARENUM {
+        // SAFETY: la vue VARIANT_0_0 est toujours valide pour lire vt
+        unsafe { self.0.Anonymous.Anonymous.vt }
+    }
+
+    /// Copie le contenu dans un SafeVariant, le VARIANT restant possédé
+    pub fn to_safe_variant(&self) -> SageResult<SafeVariant> {
+        SafeVariant::from_variant(&self.0)
+    }
+}
+
+impl Default for OwnedVariant {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for OwnedVariant {
+    fn drop(&mut self) {
+        // SAFETY: le VARIANT est possédé et valide
+        unsafe {
+            let _ = VariantClear(&mut self.0);
+        }
+    }
+}
+
+/// EXCEPINFO possédé, dont les BSTR sont libérés à la destruction
+pub struct OwnedExcepInfo(EXCEPINFO);
+
+impl OwnedExcepInfo {
+    pub fn new() -> Self {
+        OwnedExcepInfo(EXCEPINFO::default())
+    }
+
+    /// Pointeur à transmettre à `IDispatch::Invoke`
+    pub fn as_mut_ptr(&mut self) -> *mut EXCEPINFO {
+        &mut self.0
+    }
+
+    /// Complète les champs si le serveur a différé leur remplissage
+    pub fn fill_deferred(&mut self) {
+        if let Some(fill) = self.0.pfnDeferredFillIn.take() {
+            // SAFETY: fonction fournie par le serveur pour cette EXCEPINFO
+            unsafe {
+                let _ = fill(&mut self.0);
+            }
+        }
+    }
+
+    /// Source de l'exception (souvent le ProgID du composant)
+    pub fn source(&self) -> String {
+        self.0.bstrSource.to_string()
+    }
+
+    /// Description de l'erreur
+    pub fn description(&self) -> String {
+        self.0.bstrDescription.to_string()
+    }
+
+    /// Code d'erreur SCODE
+    pub fn scode(&self) -> i32 {
+        self.0.scode
+    }
+
+    /// Code d'erreur applicatif (`wCode`)
+    pub fn code(&self) -> u16 {
+        self.0.wCode
+    }
+
+    /// Fichier d'aide, vide si non renseigné
+    pub fn help_file(&self) -> String {
+        self.0.bstrHelpFile.to_string()
+    }
+
+    /// Contexte d'aide
+    pub fn help_context(&self) -> u32 {
+        self.0.dwHelpContext
+    }
+}
+
+impl Default for OwnedExcepInfo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for OwnedExcepInfo {
+    fn drop(&mut self) {
+        // SAFETY: les BSTR sont soit nuls, soit alloués par le serveur pour nous
+        unsafe {
+            ManuallyDrop::drop(&mut self.0.bstrSource);
+            ManuallyDrop::drop(&mut self.0.bstrDescription);
+            ManuallyDrop::drop(&mut self.0.bstrHelpFile);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_owned_variant_roundtrip() {
+        let owned = SafeVariant::from("Journal des ventes").to_variant().unwrap();
+        assert_eq!(owned.vt(), VT_BSTR);
+        assert_eq!(owned.to_safe_variant().unwrap().to_string().unwrap(), "Journal des ventes");
+    }
+
+    #[test]
+    fn test_into_raw_transfers_ownership() {
+        let raw = SafeVariant::from(vec!["A".to_string(), "B".to_string()]).to_variant().unwrap().into_raw();
+        // Le VARIANT est de nouveau possédé puis libéré une seule fois
+        let owned = unsafe { OwnedVariant::from_raw(raw) };
+        assert_eq!(owned.to_safe_variant().unwrap().to_array().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,140 @@
+use super::SafeVariant;
+use crate::errors::{SageError, SageResult};
+use std::fmt;
+
+/// Segment d'un chemin de propriétés, par exemple `Products(1)`
+#[derive(Debug, Clone)]
+pub struct PathSegment {
+    pub name: String,
+    /// Arguments entre parenthèses : entiers ou chaînes entre guillemets
+    pub args: Vec<SafeVariant>,
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if self.args.is_empty() {
+            return Ok(());
+        }
+        let args: Vec<String> = self
+            .args
+            .iter()
+            .map(|arg| match arg {
+                SafeVariant::BStr(s) => format!("\"{}\"", s.replace('"', "\"\"")),
+                SafeVariant::I4(i) => i.to_string(),
+                other => format!("{:?}", other),
+            })
+            .collect();
+        write!(f, "({})", args.join(", "))
+    }
+}
+
+/// Chemin pointé tel qu'écrit en VB : `Licence.Product.UserInfo.Name`,
+/// `FactoryJournal.ReadNumero("VTE").JO_Intitule` ou `Products(1).Name`
+#[derive(Debug, Clone)]
+pub struct PropertyPath {
+    segments: Vec<PathSegment>,
+}
+
+impl PropertyPath {
+    /// Analyse un chemin pointé
+    pub fn parse(path: &str) -> SageResult<Self> {
+        let mut segments = Vec::new();
+        let mut chars = path.chars().peekable();
+
+        loop {
+            let name: String = std::iter::from_fn(|| chars.next_if(|c| c.is_alphanumeric() || *c == '_')).collect();
+            if name.is_empty() {
+                return Err(invalid_path(path, "nom de membre attendu"));
+            }
+
+            let mut args = Vec::new();
+            if chars.next_if_eq(&'(').is_some() {
+                loop {
+                    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+                    if chars.next_if_eq(&')').is_some() && args.is_empty() {
+                        break;
+                    }
+                    args.push(parse_arg(path, &mut chars)?);
+                    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+                    match chars.next() {
+                        Some(',') => continue,
+                        Some(')') => break,
+                        _ => return Err(invalid_path(path, "`,` ou `)` attendu")),
+                    }
+                }
+            }
+            segments.push(PathSegment { name, args });
+
+            match chars.next() {
+                None => break,
+                Some('.') => continue,
+                Some(c) => return Err(invalid_path(path, &format!("caractère inattendu `{}`", c))),
+            }
+        }
+
+        Ok(PropertyPath { segments })
+    }
+
+    /// Segments dans l'ordre du parcours
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.segments
+    }
+}
+
+impl fmt::Display for PropertyPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let segments: Vec<String> = self.segments.iter().map(|s| s.to_string()).collect();
+        write!(f, "{}", segments.join("."))
+    }
+}
+
+fn parse_arg(path: &str, chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> SageResult<SafeVariant> {
+    if chars.next_if_eq(&'"').is_some() {
+        // Chaîne VB : `""` représente un guillemet
+        let mut text = String::new();
+        loop {
+            match chars.next() {
+                Some('"') if chars.next_if_eq(&'"').is_some() => text.push('"'),
+                Some('"') => return Ok(SafeVariant::BStr(text)),
+                Some(c) => text.push(c),
+                None => return Err(invalid_path(path, "chaîne non terminée")),
+            }
+        }
+    }
+
+    let digits: String = std::iter::from_fn(|| chars.next_if(|c| c.is_ascii_digit() || *c == '-')).collect();
+    digits
+        .parse::<i32>()
+        .map(SafeVariant::I4)
+        .map_err(|_| invalid_path(path, "argument entier ou chaîne entre guillemets attendu"))
+}
+
+fn invalid_path(path: &str, expected: &str) -> SageError {
+    SageError::InvalidParameter {
+        parameter: "path".to_string(),
+        expected: expected.to_string(),
+        received: path.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_segments_and_arguments() {
+        let path = PropertyPath::parse("Licence.Products(1).Item(\"Nom \"\"court\"\"\", -2).Name").unwrap();
+        let segments = path.segments();
+
+        assert_eq!(segments.len(), 4);
+        assert!(segments[0].args.is_empty());
+        assert!(matches!(segments[1].args[..], [SafeVariant::I4(1)]));
+        assert!(matches!(&segments[2].args[..], [SafeVariant::BStr(s), SafeVariant::I4(-2)] if s == "Nom \"court\""));
+        assert_eq!(PropertyPath::parse("Products( 1 ).Name").unwrap().to_string(), "Products(1).Name");
+
+        for invalid in ["", "Licence.", "Products(1", "Products(x)", "A..B", "Name\"x\""] {
+            assert!(PropertyPath::parse(invalid).is_err(), "{}", invalid);
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use crate::errors::SageResult;
+use std::collections::BTreeMap;
+
+/// Lecture de la base de registre (HKEY_CLASSES_ROOT), abstraite pour les tests
+///
+/// Les chemins sont relatifs à HKEY_CLASSES_ROOT, séparés par `\` et
+/// insensibles à la casse comme dans le registre Windows. Une clé absente
+/// n'est pas une erreur : elle n'a ni sous-clé ni valeur.
+pub trait RegistryReader {
+    /// Noms des sous-clés directes de `path` (`""` pour la racine)
+    fn subkeys(&self, path: &str) -> SageResult<Vec<String>>;
+
+    /// Valeur par défaut (texte) de la clé `path`
+    fn default_value(&self, path: &str) -> SageResult<Option<String>>;
+}
+
+/// Registre en mémoire, pour décrire une installation Sage dans les tests
+///
+/// ```ignore
+/// let registry = MemoryRegistry::new()
+///     .with_key(r"Objets100c.Cpta.Stream.1\CLSID", Some("{309DE0FB-...}"))
+///     .with_key(r"CLSID\{309DE0FB-...}\TypeLib", Some("{...}"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MemoryRegistry {
+    /// Chemin en minuscules -> (chemin d'origine, valeur par défaut)
+    keys: BTreeMap<String, (String, Option<String>)>,
+}
+
+impl MemoryRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ajoute une clé (et implicitement ses parents) avec sa valeur par défaut
+    pub fn with_key(mut self, path: &str, default_value: Option<&str>) -> Self {
+        self.keys.insert(
+            path.to_lowercase(),
+            (path.to_string(), default_value.map(str::to_string)),
+        );
+        self
+    }
+}
+
+impl RegistryReader for MemoryRegistry {
+    fn subkeys(&self, path: &str) -> SageResult<Vec<String>> {
+        let prefix = if path.is_empty() {
+            String::new()
+        } else {
+            format!("{}\\", path.to_lowercase())
+        };
+
+        let mut names: Vec<String> = Vec::new();
+        for (lower, (original, _)) in self.keys.range(prefix.clone()..) {
+            if !lower.starts_with(&prefix) {
+                break;
+            }
+            let name = original[prefix.len()..].split('\\').next().unwrap_or_default();
+            if !name.is_empty() && !names.iter().any(|n| n.eq_ignore_ascii_case(name)) {
+                names.push(name.to_string());
+            }
+        }
+        Ok(names)
+    }
+
+    fn default_value(&self, path: &str) -> SageResult<Option<String>> {
+        Ok(self
+            .keys
+            .get(&path.to_lowercase())
+            .and_then(|(_, value)| value.clone()))
+    }
+}
+
+#[cfg(all(windows, feature = "com"))]
+pub use windows_registry::WindowsRegistry;
+
+#[cfg(all(windows, feature = "com"))]
+mod windows_registry {
+    use super::RegistryReader;
+    use crate::errors::{SageError, SageResult};
+    use windows::core::{HSTRING, PCWSTR, PWSTR};
+    use windows::Win32::Foundation::{ERROR_FILE_NOT_FOUND, ERROR_NO_MORE_ITEMS};
+    use windows::Win32::System::Registry::{
+        RegCloseKey, RegEnumKeyExW, RegOpenKeyExW, RegQueryValueExW, HKEY, HKEY_CLASSES_ROOT, KEY_READ,
+    };
+
+    /// Registre Windows réel (HKEY_CLASSES_ROOT)
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct WindowsRegistry;
+
+    /// Clé ouverte, refermée à la libération
+    struct OpenKey(HKEY);
+
+    impl Drop for OpenKey {
+        fn drop(&mut self) {
+            unsafe {
+                let _ = RegCloseKey(self.0);
+            }
+        }
+    }
+
+    fn open(path: &str) -> SageResult<Option<OpenKey>> {
+        let mut key = HKEY::default();
+        match unsafe { RegOpenKeyExW(HKEY_CLASSES_ROOT, &HSTRING::from(path), 0, KEY_READ, &mut key) } {
+            Ok(()) => Ok(Some(OpenKey(key))),
+            Err(error) if error.code() == ERROR_FILE_NOT_FOUND.to_hresult() => Ok(None),
+            Err(error) => Err(SageError::from(error)),
+        }
+    }
+
+    impl RegistryReader for WindowsRegistry {
+        fn subkeys(&self, path: &str) -> SageResult<Vec<String>> {
+            let Some(key) = open(path)? else {
+                return Ok(Vec::new());
+            };
+
+            let mut names = Vec::new();
+            // Longueur maximale d'un nom de clé : 255 caractères
+            let mut buffer = [0u16; 256];
+            for index in 0.. {
+                let mut len = buffer.len() as u32;
+                let result = unsafe {
+                    RegEnumKeyExW(key.0, index, PWSTR(buffer.as_mut_ptr()), &mut len, None, PWSTR::null(), None, None)
+                };
+                match result {
+                    Ok(()) => names.push(String::from_utf16_lossy(&buffer[..len as usize])),
+                    Err(error) if error.code() == ERROR_NO_MORE_ITEMS.to_hresult() => break,
+                    Err(error) => return Err(SageError::from(error)),
+                }
+            }
+            Ok(names)
+        }
+
+        fn default_value(&self, path: &str) -> SageResult<Option<String>> {
+            let Some(key) = open(path)? else {
+                return Ok(None);
+            };
+
+            let mut size = 0u32;
+            let query = unsafe { RegQueryValueExW(key.0, PCWSTR::null(), None, None, None, Some(&mut size)) };
+            if query.is_err() || size == 0 {
+                return Ok(None);
+            }
+
+            let mut buffer = vec![0u16; (size as usize).div_ceil(2)];
+            unsafe {
+                RegQueryValueExW(
+                    key.0,
+                    PCWSTR::null(),
+                    None,
+                    None,
+                    Some(buffer.as_mut_ptr() as *mut u8),
+                    Some(&mut size),
+                )?;
+            }
+            let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+            Ok(Some(String::from_utf16_lossy(&buffer[..len])))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_registry_is_case_insensitive() {
+        let registry = MemoryRegistry::new()
+            .with_key(r"CLSID\{AAA}\TypeLib", Some("{BBB}"))
+            .with_key(r"CLSID\{CCC}", None)
+            .with_key(r"Objets100c.Cpta.Stream.1", Some("BSCPTAApplication100c"));
+
+        assert_eq!(registry.subkeys("").unwrap(), vec!["CLSID", "Objets100c.Cpta.Stream.1"]);
+        assert_eq!(registry.subkeys("clsid").unwrap(), vec!["{AAA}", "{CCC}"]);
+        assert_eq!(registry.default_value(r"clsid\{aaa}\typelib").unwrap().as_deref(), Some("{BBB}"));
+        assert_eq!(registry.default_value(r"CLSID\{CCC}").unwrap(), None);
+        assert!(registry.subkeys("Inconnue").unwrap().is_empty());
+    }
+}
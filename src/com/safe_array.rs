@@ -0,0 +1,220 @@
+use super::SafeVariant;
+use crate::errors::{SageError, SageResult};
+use serde::{Deserialize, Serialize};
+
+/// Bornes d'une dimension de tableau (équivalent SAFEARRAYBOUND)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArrayBound {
+    /// Borne inférieure (0 en C#, souvent 1 en VB)
+    pub lower: i32,
+    /// Nombre d'éléments de la dimension
+    pub len: u32,
+}
+
+impl ArrayBound {
+    pub fn new(lower: i32, len: u32) -> Self {
+        ArrayBound { lower, len }
+    }
+
+    /// Borne supérieure incluse (équivalent UBound)
+    pub fn upper(&self) -> i32 {
+        self.lower + self.len as i32 - 1
+    }
+}
+
+/// Éléments typés d'un tableau, dans l'ordre mémoire du SAFEARRAY
+///
+/// Sérialisé avec le type des éléments : `{"type":"I4","values":[1,2]}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "values")]
+pub enum ArrayData {
+    Variant(Vec<SafeVariant>),
+    BStr(Vec<String>),
+    I4(Vec<i32>),
+    R8(Vec<f64>),
+    /// Tableau d'octets (blob binaire)
+    UI1(Vec<u8>),
+}
+
+impl ArrayData {
+    /// Nombre total d'éléments
+    pub fn len(&self) -> usize {
+        match self {
+            ArrayData::Variant(v) => v.len(),
+            ArrayData::BStr(v) => v.len(),
+            ArrayData::I4(v) => v.len(),
+            ArrayData::R8(v) => v.len(),
+            ArrayData::UI1(v) => v.len(),
+        }
+    }
+
+    /// Vérifie si le tableau est vide
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Nom du type des éléments
+    pub fn element_type_name(&self) -> &'static str {
+        match self {
+            ArrayData::Variant(_) => "Variant",
+            ArrayData::BStr(_) => "BStr",
+            ArrayData::I4(_) => "I4",
+            ArrayData::R8(_) => "R8",
+            ArrayData::UI1(_) => "UI1",
+        }
+    }
+
+    /// Élément à la position mémoire `index`, converti en SafeVariant
+    pub fn get(&self, index: usize) -> Option<SafeVariant> {
+        match self {
+            ArrayData::Variant(v) => v.get(index).cloned(),
+            ArrayData::BStr(v) => v.get(index).map(|s| SafeVariant::BStr(s.clone())),
+            ArrayData::I4(v) => v.get(index).map(|i| SafeVariant::I4(*i)),
+            ArrayData::R8(v) => v.get(index).map(|f| SafeVariant::R8(*f)),
+            ArrayData::UI1(v) => v.get(index).map(|b| SafeVariant::UI1(*b)),
+        }
+    }
+}
+
+/// Tableau COM (SAFEARRAY) à une ou plusieurs dimensions
+///
+/// Les éléments sont rangés comme dans un SAFEARRAY : la première dimension
+/// varie le plus vite (ordre colonne des tableaux VB).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "SafeArrayRepr")]
+pub struct SafeArray {
+    bounds: Vec<ArrayBound>,
+    data: ArrayData,
+}
+
+/// Forme désérialisée, validée par `SafeArray::new`
+#[derive(Deserialize)]
+struct SafeArrayRepr {
+    bounds: Vec<ArrayBound>,
+    data: ArrayData,
+}
+
+impl TryFrom<SafeArrayRepr> for SafeArray {
+    type Error = SageError;
+
+    fn try_from(repr: SafeArrayRepr) -> SageResult<Self> {
+        SafeArray::new(repr.bounds, repr.data)
+    }
+}
+
+impl SafeArray {
+    /// Crée un tableau en vérifiant la cohérence bornes / nombre d'éléments
+    pub fn new(bounds: Vec<ArrayBound>, data: ArrayData) -> SageResult<Self> {
+        let expected: usize = bounds.iter().map(|b| b.len as usize).product();
+        if bounds.is_empty() || expected != data.len() {
+            return Err(SageError::invalid_parameter(
+                "bounds",
+                &format!("{} élément(s)", data.len()),
+                &format!("{} dimension(s) pour {} élément(s)", bounds.len(), expected),
+            ));
+        }
+        Ok(SafeArray { bounds, data })
+    }
+
+    /// Crée un tableau à une dimension indexé à partir de 0
+    pub fn from_data(data: ArrayData) -> Self {
+        SafeArray {
+            bounds: vec![ArrayBound::new(0, data.len() as u32)],
+            data,
+        }
+    }
+
+    /// Bornes de chaque dimension, de la première à la dernière
+    pub fn bounds(&self) -> &[ArrayBound] {
+        &self.bounds
+    }
+
+    /// Éléments typés
+    pub fn data(&self) -> &ArrayData {
+        &self.data
+    }
+
+    /// Consomme le tableau et retourne ses éléments
+    pub fn into_data(self) -> ArrayData {
+        self.data
+    }
+
+    /// Nombre de dimensions
+    pub fn dims(&self) -> usize {
+        self.bounds.len()
+    }
+
+    /// Nombre total d'éléments
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Vérifie si le tableau est vide
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Élément aux indices donnés (un par dimension, bornes incluses)
+    pub fn get(&self, indices: &[i32]) -> Option<SafeVariant> {
+        if indices.len() != self.bounds.len() {
+            return None;
+        }
+
+        let mut offset = 0usize;
+        let mut stride = 1usize;
+        for (index, bound) in indices.iter().zip(&self.bounds) {
+            if *index < bound.lower || *index > bound.upper() {
+                return None;
+            }
+            offset += (*index - bound.lower) as usize * stride;
+            stride *= bound.len as usize;
+        }
+
+        self.data.get(offset)
+    }
+
+    /// Tous les éléments convertis en SafeVariant, dans l'ordre mémoire
+    pub fn to_variants(&self) -> Vec<SafeVariant> {
+        (0..self.len()).filter_map(|i| self.data.get(i)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_one_dimension_bounds() {
+        let array = SafeArray::new(
+            vec![ArrayBound::new(1, 3)],
+            ArrayData::BStr(vec!["VTE".into(), "ACH".into(), "BQ1".into()]),
+        )
+        .unwrap();
+
+        assert_eq!(array.bounds()[0].upper(), 3);
+        assert_eq!(array.get(&[1]).unwrap().to_string().unwrap(), "VTE");
+        assert_eq!(array.get(&[3]).unwrap().to_string().unwrap(), "BQ1");
+        assert!(array.get(&[0]).is_none());
+    }
+
+    #[test]
+    fn test_multi_dimension_column_order() {
+        // Tableau 2x3 : la première dimension varie le plus vite
+        let array = SafeArray::new(
+            vec![ArrayBound::new(0, 2), ArrayBound::new(0, 3)],
+            ArrayData::I4(vec![0, 1, 10, 11, 20, 21]),
+        )
+        .unwrap();
+
+        assert_eq!(array.dims(), 2);
+        assert_eq!(array.get(&[1, 0]).unwrap().to_i32().unwrap(), 1);
+        assert_eq!(array.get(&[0, 2]).unwrap().to_i32().unwrap(), 20);
+        assert_eq!(array.get(&[1, 2]).unwrap().to_i32().unwrap(), 21);
+    }
+
+    #[test]
+    fn test_inconsistent_bounds() {
+        let result = SafeArray::new(vec![ArrayBound::new(0, 4)], ArrayData::UI1(vec![1, 2]));
+        assert!(result.is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::errors::{SageError, SageResult};
+#[cfg(all(windows, feature = "com"))]
 use windows::core::BSTR;
 
 /// Wrapper sûr pour les chaînes BSTR COM
@@ -18,6 +19,7 @@ impl SafeString {
     }
 
     /// Crée une SafeString à partir d'un BSTR Windows
+    #[cfg(all(windows, feature = "com"))]
     pub fn from_bstr(bstr: BSTR) -> Self {
         SafeString {
             inner: bstr.to_string(),
@@ -25,6 +27,7 @@ impl SafeString {
     }
 
     /// Convertit vers BSTR Windows
+    #[cfg(all(windows, feature = "com"))]
     pub fn to_bstr(&self) -> BSTR {
         BSTR::from(self.inner.as_str())
     }
@@ -179,6 +182,7 @@ impl From<&str> for SafeString {
     }
 }
 
+#[cfg(all(windows, feature = "com"))]
 impl From<BSTR> for SafeString {
     fn from(bstr: BSTR) -> Self {
         SafeString::from_bstr(bstr)
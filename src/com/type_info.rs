@@ -0,0 +1,254 @@
+use super::{MemberInfo, MemberType, ParamInfo, SafeVariant};
+use crate::errors::SageResult;
+use windows::{core::*, Win32::System::Com::*, Win32::System::Ole::*, Win32::System::Variant::*};
+
+/// Décrit tous les membres d'une interface à partir de son ITypeInfo
+///
+/// Parcourt les `cFuncs` FUNCDESC et les `cVars` VARDESC de la TYPEATTR :
+/// chaque accesseur (get/put/putref) d'une propriété donne un `MemberInfo`
+/// distinct, comme dans la bibliothèque de types. Les membres restreints
+/// (QueryInterface, Invoke...) sont ignorés.
+pub fn describe_members(type_info: &ITypeInfo) -> SageResult<Vec<MemberInfo>> {
+    let (func_count, var_count) = unsafe {
+        let attr = type_info.GetTypeAttr()?;
+        let counts = ((*attr).cFuncs, (*attr).cVars);
+        type_info.ReleaseTypeAttr(attr);
+        counts
+    };
+
+    let mut members = Vec::with_capacity(func_count as usize + var_count as usize);
+
+    for index in 0..func_count as u32 {
+        let desc = unsafe { type_info.GetFuncDesc(index)? };
+        let member = unsafe { describe_function(type_info, &*desc) };
+        unsafe { type_info.ReleaseFuncDesc(desc) };
+        if let Some(member) = member? {
+            members.push(member);
+        }
+    }
+
+    for index in 0..var_count as u32 {
+        let desc = unsafe { type_info.GetVarDesc(index)? };
+        let described = unsafe { describe_variable(type_info, &*desc) };
+        unsafe { type_info.ReleaseVarDesc(desc) };
+        members.extend(described?);
+    }
+
+    Ok(members)
+}
+
+/// Décrit une fonction (méthode ou accesseur de propriété)
+unsafe fn describe_function(type_info: &ITypeInfo, desc: &FUNCDESC) -> SageResult<Option<MemberInfo>> {
+    if desc.wFuncFlags.0 & FUNCFLAG_FRESTRICTED.0 != 0 {
+        return Ok(None);
+    }
+
+    let member_type = match desc.invkind {
+        INVOKE_PROPERTYGET => MemberType::PropertyGet,
+        INVOKE_PROPERTYPUT => MemberType::PropertyPut,
+        INVOKE_PROPERTYPUTREF => MemberType::PropertyPutRef,
+        _ => MemberType::Method,
+    };
+
+    // Nom de la fonction suivi des noms de paramètres
+    let param_total = desc.cParams.max(0) as usize;
+    let mut names = vec![BSTR::default(); param_total + 1];
+    let mut name_count = 0u32;
+    unsafe { type_info.GetNames(desc.memid, &mut names, &mut name_count)? };
+    let name = names[0].to_string();
+
+    let elems: &[ELEMDESC] = if param_total == 0 || desc.lprgelemdescParam.is_null() {
+        &[]
+    } else {
+        unsafe { std::slice::from_raw_parts(desc.lprgelemdescParam, param_total) }
+    };
+
+    // Sans cParamsOpt explicite, seuls les [optional] sont facultatifs ; -1 = vararg
+    let first_optional = if desc.cParamsOpt > 0 {
+        param_total.saturating_sub(desc.cParamsOpt as usize)
+    } else {
+        param_total
+    };
+
+    let mut params = Vec::with_capacity(param_total);
+    let mut return_type = unsafe { type_name(type_info, &desc.elemdescFunc.tdesc) };
+
+    for (index, elem) in elems.iter().enumerate() {
+        let param_desc = unsafe { elem.Anonymous.paramdesc };
+        let flags = param_desc.wParamFlags.0;
+
+        // Interface non dispatch : le [retval] porte le vrai type de retour
+        if flags & PARAMFLAG_FRETVAL.0 != 0 {
+            return_type = unsafe { pointee_type_name(type_info, &elem.tdesc) };
+            continue;
+        }
+
+        let default_value = if flags & PARAMFLAG_FHASDEFAULT.0 != 0 && !param_desc.pparamdescex.is_null() {
+            let value = unsafe { &(*param_desc.pparamdescex).varDefaultValue };
+            SafeVariant::from_variant(value).and_then(|v| v.to_string()).ok()
+        } else {
+            None
+        };
+
+        // Un PROPPUT ne nomme pas son dernier paramètre (la valeur affectée)
+        let param_name = names
+            .get(index + 1)
+            .filter(|_| (index + 1) < name_count as usize)
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| if member_type == MemberType::Method { format!("param{}", index) } else { "value".to_string() });
+
+        params.push(ParamInfo {
+            name: param_name,
+            type_name: unsafe { type_name(type_info, &elem.tdesc) },
+            optional: flags & PARAMFLAG_FOPT.0 != 0 || default_value.is_some() || index >= first_optional,
+            default_value,
+            is_out: flags & PARAMFLAG_FOUT.0 != 0,
+        });
+    }
+
+    if return_type == "hresult" {
+        return_type = "void".to_string();
+    }
+
+    Ok(Some(MemberInfo {
+        id: desc.memid,
+        name,
+        member_type,
+        param_count: Some(params.len() as u32),
+        return_type: Some(return_type),
+        params,
+        description: unsafe { doc_string(type_info, desc.memid) },
+    }))
+}
+
+/// Décrit une propriété de dispinterface (VARDESC) : un get, plus un put si modifiable
+unsafe fn describe_variable(type_info: &ITypeInfo, desc: &VARDESC) -> SageResult<Vec<MemberInfo>> {
+    if desc.wVarFlags.0 & VARFLAG_FRESTRICTED.0 != 0 || desc.varkind == VAR_CONST {
+        return Ok(Vec::new());
+    }
+
+    let mut names = [BSTR::default()];
+    let mut name_count = 0u32;
+    unsafe { type_info.GetNames(desc.memid, &mut names, &mut name_count)? };
+
+    let value_type = unsafe { type_name(type_info, &desc.elemdescVar.tdesc) };
+    let description = unsafe { doc_string(type_info, desc.memid) };
+    let getter = MemberInfo {
+        id: desc.memid,
+        name: names[0].to_string(),
+        member_type: MemberType::PropertyGet,
+        param_count: Some(0),
+        return_type: Some(value_type.clone()),
+        params: Vec::new(),
+        description,
+    };
+
+    let mut members = vec![getter.clone()];
+    if desc.wVarFlags.0 & VARFLAG_FREADONLY.0 == 0 {
+        members.push(MemberInfo {
+            member_type: MemberType::PropertyPut,
+            param_count: Some(1),
+            return_type: Some("void".to_string()),
+            params: vec![ParamInfo {
+                name: "value".to_string(),
+                type_name: value_type,
+                optional: false,
+                default_value: None,
+                is_out: false,
+            }],
+            ..getter
+        });
+    }
+
+    Ok(members)
+}
+
+/// Chaîne d'aide d'un membre
+unsafe fn doc_string(type_info: &ITypeInfo, memid: i32) -> Option<String> {
+    let mut doc = BSTR::default();
+    unsafe {
+        type_info
+            .GetDocumentation(memid, None, Some(&mut doc), std::ptr::null_mut(), None)
+            .ok()?;
+    }
+    Some(doc.to_string()).filter(|d| !d.is_empty())
+}
+
+/// Type pointé par un `[out, retval] T*`
+unsafe fn pointee_type_name(type_info: &ITypeInfo, desc: &TYPEDESC) -> String {
+    if desc.vt == VT_PTR && !unsafe { desc.Anonymous.lptdesc }.is_null() {
+        unsafe { type_name(type_info, &*desc.Anonymous.lptdesc) }
+    } else {
+        unsafe { type_name(type_info, desc) }
+    }
+}
+
+/// Nom lisible d'un TYPEDESC, en résolvant les types utilisateur (interfaces, enums)
+///
+/// # Safety
+/// `desc` doit provenir d'un FUNCDESC/VARDESC de `type_info` encore non libéré.
+pub unsafe fn type_name(type_info: &ITypeInfo, desc: &TYPEDESC) -> String {
+    match desc.vt {
+        VT_PTR | VT_SAFEARRAY => {
+            let inner = unsafe { desc.Anonymous.lptdesc };
+            if inner.is_null() {
+                return vt_name(desc.vt);
+            }
+            let inner = unsafe { type_name(type_info, &*inner) };
+            if desc.vt == VT_PTR { format!("{}*", inner) } else { format!("array<{}>", inner) }
+        }
+        VT_CARRAY => {
+            let array = unsafe { desc.Anonymous.lpadesc };
+            if array.is_null() {
+                return vt_name(desc.vt);
+            }
+            format!("array<{}>", unsafe { type_name(type_info, &(*array).tdescElem) })
+        }
+        VT_USERDEFINED => {
+            let href = unsafe { desc.Anonymous.hreftype };
+            let mut name = BSTR::default();
+            let resolved = unsafe {
+                type_info.GetRefTypeInfo(href).and_then(|referenced| {
+                    referenced.GetDocumentation(-1, Some(&mut name), None, std::ptr::null_mut(), None)
+                })
+            };
+            match resolved {
+                Ok(()) => name.to_string(),
+                Err(_) => "userdefined".to_string(),
+            }
+        }
+        vt => vt_name(vt),
+    }
+}
+
+/// Convertit le VARTYPE en nom de type lisible
+pub fn vt_name(vt: VARENUM) -> String {
+    let type_name = match vt {
+        VT_EMPTY | VT_VOID => "void",
+        VT_NULL => "null",
+        VT_I1 => "char",
+        VT_I2 => "short",
+        VT_I4 | VT_INT => "long",
+        VT_R4 => "float",
+        VT_R8 => "double",
+        VT_CY => "currency",
+        VT_DATE => "date",
+        VT_BSTR | VT_LPSTR | VT_LPWSTR => "string",
+        VT_DISPATCH => "object",
+        VT_ERROR => "error",
+        VT_BOOL => "bool",
+        VT_VARIANT => "variant",
+        VT_UNKNOWN => "unknown",
+        VT_DECIMAL => "decimal",
+        VT_UI1 => "byte",
+        VT_UI2 => "ushort",
+        VT_UI4 | VT_UINT => "ulong",
+        VT_I8 => "longlong",
+        VT_UI8 => "ulonglong",
+        VT_HRESULT => "hresult",
+        VT_PTR => "pointer",
+        VT_SAFEARRAY => "array",
+        other => return format!("VT_{}", other.0),
+    };
+    type_name.to_string()
+}
@@ -0,0 +1,500 @@
+use super::discovery::SageVersion;
+use super::MemberInfo;
+use serde::{Deserialize, Serialize};
+
+/// Nature d'un type déclaré dans une bibliothèque de types (TYPEKIND)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TypeKind {
+    Enum,
+    Record,
+    Module,
+    /// Interface vtable (`TKIND_INTERFACE`)
+    Interface,
+    /// Dispinterface, y compris la face dispatch d'une interface duale
+    DispInterface,
+    CoClass,
+    Alias,
+    Union,
+}
+
+/// Constante d'une énumération (`EcritureSensTypeDebit = 0`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnumValue {
+    pub name: String,
+    pub value: i32,
+    pub description: Option<String>,
+}
+
+/// Constante d'un module, de n'importe quel type (chaîne, `UI4`...)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConstantInfo {
+    pub name: String,
+    /// Type lisible, comme pour `ParamInfo::type_name`
+    pub type_name: String,
+    /// Valeur convertie en texte, `None` si elle n'est pas représentable
+    pub value: Option<String>,
+    pub description: Option<String>,
+}
+
+/// Champ d'une structure ou d'une union
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldInfo {
+    pub name: String,
+    /// Type lisible, comme pour `ParamInfo::type_name`
+    pub type_name: String,
+    pub description: Option<String>,
+}
+
+/// Interface implémentée par une coclasse, ou héritée par une interface
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImplementedInterface {
+    pub name: String,
+    /// Interface `[default]` de la coclasse
+    pub is_default: bool,
+    /// Interface d'événements (`[source]`)
+    pub is_source: bool,
+}
+
+/// Type déclaré dans la bibliothèque
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TypeEntry {
+    pub name: String,
+    /// GUID avec accolades ; nul (`{00000000-...}`) pour les enums et alias sans `uuid`
+    pub guid: String,
+    pub kind: TypeKind,
+    /// Interface duale (`TYPEFLAG_FDUAL`)
+    pub is_dual: bool,
+    /// Type caché (`TYPEFLAG_FHIDDEN`), absent des navigateurs d'objets
+    pub is_hidden: bool,
+    pub description: Option<String>,
+    /// Membres d'une interface, dispinterface ou d'un module
+    pub members: Vec<MemberInfo>,
+    /// Constantes d'une énumération
+    pub values: Vec<EnumValue>,
+    /// Constantes d'un module, quel que soit leur type
+    #[serde(default)]
+    pub constants: Vec<ConstantInfo>,
+    /// Champs d'une structure ou d'une union
+    pub fields: Vec<FieldInfo>,
+    /// Interfaces d'une coclasse, ou interface de base
+    pub interfaces: Vec<ImplementedInterface>,
+    /// Type désigné par un alias (`typedef`)
+    pub alias_of: Option<String>,
+}
+
+impl TypeEntry {
+    /// Valeur d'une constante d'énumération, sans tenir compte de la casse
+    pub fn value(&self, name: &str) -> Option<i32> {
+        self.values
+            .iter()
+            .find(|v| v.name.eq_ignore_ascii_case(name))
+            .map(|v| v.value)
+    }
+
+    /// Nom de la constante portant `value`
+    pub fn value_name(&self, value: i32) -> Option<&str> {
+        self.values.iter().find(|v| v.value == value).map(|v| v.name.as_str())
+    }
+
+    /// Interface `[default]` d'une coclasse
+    pub fn default_interface(&self) -> Option<&str> {
+        self.interfaces
+            .iter()
+            .find(|i| i.is_default && !i.is_source)
+            .map(|i| i.name.as_str())
+    }
+}
+
+/// Contenu d'une bibliothèque de types, sérialisable pour consultation hors ligne
+///
+/// ```ignore
+/// let class = newest_class(&WindowsRegistry, SageModule::Cpta)?;
+/// let typelib = TypeLibrary::for_class(&class)?;
+/// let sens = typelib.find("EcritureSensType").unwrap();
+/// println!("{}", serde_json::to_string_pretty(&typelib)?);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TypeLibrary {
+    pub name: String,
+    /// GUID avec accolades, tel qu'enregistré sous `HKCR\TypeLib`
+    pub guid: String,
+    pub version: SageVersion,
+    pub description: Option<String>,
+    /// Types dans l'ordre de la bibliothèque
+    pub types: Vec<TypeEntry>,
+}
+
+impl TypeLibrary {
+    /// Type nommé, sans tenir compte de la casse
+    pub fn find(&self, name: &str) -> Option<&TypeEntry> {
+        self.types.iter().find(|t| t.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Types d'une nature donnée
+    pub fn of_kind(&self, kind: TypeKind) -> impl Iterator<Item = &TypeEntry> {
+        self.types.iter().filter(move |t| t.kind == kind)
+    }
+
+    /// Énumérations de la bibliothèque (sens, types de compte, types de document...)
+    pub fn enums(&self) -> impl Iterator<Item = &TypeEntry> {
+        self.of_kind(TypeKind::Enum)
+    }
+
+    /// Valeur de `value` dans l'énumération `enum_name`
+    pub fn enum_value(&self, enum_name: &str, value: &str) -> Option<i32> {
+        self.find(enum_name).and_then(|t| t.value(value))
+    }
+}
+
+#[cfg(all(windows, feature = "com"))]
+mod loader {
+    use super::*;
+    use crate::com::discovery::SageClassInfo;
+    use crate::com::type_info::{describe_members, type_name};
+    use crate::com::{ComInstance, SafeVariant};
+    use crate::errors::{SageError, SageResult};
+    use windows::core::{BSTR, GUID, HSTRING};
+    use windows::Win32::System::Com::*;
+    use windows::Win32::System::Ole::*;
+
+    impl TypeLibrary {
+        /// Charge une bibliothèque enregistrée (`HKCR\TypeLib\{guid}\<version>`)
+        pub fn load_registered(guid: &str, version: SageVersion) -> SageResult<Self> {
+            let guid = ComInstance::parse_clsid(guid)?;
+            let library = unsafe { LoadRegTypeLib(&guid, version.major, version.minor, 0)? };
+            Self::from_type_lib(&library)
+        }
+
+        /// Charge la bibliothèque embarquée dans une DLL ou un fichier `.tlb`, sans l'enregistrer
+        pub fn load_file(path: &str) -> SageResult<Self> {
+            let library = unsafe { LoadTypeLibEx(&HSTRING::from(path), REGKIND_NONE)? };
+            Self::from_type_lib(&library)
+        }
+
+        /// Bibliothèque d'une classe découverte dans le registre
+        ///
+        /// La bibliothèque enregistrée est préférée ; à défaut, celle de la DLL serveur.
+        pub fn for_class(class: &SageClassInfo) -> SageResult<Self> {
+            match (&class.typelib, class.version, &class.server) {
+                (Some(guid), Some(version), _) => Self::load_registered(guid, version),
+                (_, _, Some(server)) => Self::load_file(server),
+                _ => Err(SageError::class_not_registered(format!(
+                    "Aucune bibliothèque de types déclarée pour {}",
+                    class.progid
+                ))),
+            }
+        }
+
+        /// Bibliothèque contenant l'interface d'un objet d'automation
+        pub fn of_dispatch(dispatch: &IDispatch) -> SageResult<Self> {
+            let mut library: Option<ITypeLib> = None;
+            let mut index = 0u32;
+            unsafe {
+                let type_info = dispatch.GetTypeInfo(0, 0)?;
+                type_info.GetContainingTypeLib(&mut library, &mut index)?;
+            }
+            let library = library.ok_or_else(|| {
+                SageError::UnsupportedOperation("L'objet n'expose pas de bibliothèque de types".to_string())
+            })?;
+            Self::from_type_lib(&library)
+        }
+
+        /// Parcourt tous les types d'une bibliothèque déjà chargée
+        pub fn from_type_lib(library: &ITypeLib) -> SageResult<Self> {
+            let (guid, version) = unsafe {
+                let attr = library.GetLibAttr()?;
+                let read = (
+                    (*attr).guid,
+                    SageVersion {
+                        major: (*attr).wMajorVerNum,
+                        minor: (*attr).wMinorVerNum,
+                    },
+                );
+                library.ReleaseTLibAttr(attr);
+                read
+            };
+
+            let mut name = BSTR::default();
+            let mut doc = BSTR::default();
+            unsafe {
+                library.GetDocumentation(-1, Some(&mut name), Some(&mut doc), std::ptr::null_mut(), None)?;
+            }
+
+            let count = unsafe { library.GetTypeInfoCount() };
+            let mut types = Vec::with_capacity(count as usize);
+            for index in 0..count {
+                let type_info = unsafe { library.GetTypeInfo(index)? };
+                types.push(describe_type(&type_info)?);
+            }
+
+            Ok(TypeLibrary {
+                name: name.to_string(),
+                guid: guid_string(&guid),
+                version,
+                description: non_empty(doc),
+                types,
+            })
+        }
+    }
+
+    /// Décrit un type selon sa nature
+    fn describe_type(type_info: &ITypeInfo) -> SageResult<TypeEntry> {
+        let (guid, typekind, var_count, impl_count, flags, alias_of) = unsafe {
+            let attr = type_info.GetTypeAttr()?;
+            let read = (
+                (*attr).guid,
+                (*attr).typekind,
+                (*attr).cVars,
+                (*attr).cImplTypes,
+                (*attr).wTypeFlags as i32,
+                // Le TYPEDESC de l'alias n'est valide qu'avant ReleaseTypeAttr
+                ((*attr).typekind == TKIND_ALIAS).then(|| type_name(type_info, &(*attr).tdescAlias)),
+            );
+            type_info.ReleaseTypeAttr(attr);
+            read
+        };
+
+        let kind = match typekind {
+            TKIND_ENUM => TypeKind::Enum,
+            TKIND_RECORD => TypeKind::Record,
+            TKIND_MODULE => TypeKind::Module,
+            TKIND_INTERFACE => TypeKind::Interface,
+            TKIND_DISPATCH => TypeKind::DispInterface,
+            TKIND_COCLASS => TypeKind::CoClass,
+            TKIND_ALIAS => TypeKind::Alias,
+            _ => TypeKind::Union,
+        };
+        let (name, description) = documentation(type_info, MEMBERID_NIL);
+
+        let mut entry = TypeEntry {
+            name,
+            guid: guid_string(&guid),
+            kind,
+            is_dual: flags & TYPEFLAG_FDUAL.0 != 0,
+            is_hidden: flags & TYPEFLAG_FHIDDEN.0 != 0,
+            description,
+            members: Vec::new(),
+            values: Vec::new(),
+            constants: Vec::new(),
+            fields: Vec::new(),
+            interfaces: Vec::new(),
+            alias_of,
+        };
+
+        match kind {
+            TypeKind::Enum => entry.values = variables(type_info, var_count)?.values,
+            TypeKind::Record | TypeKind::Union => entry.fields = variables(type_info, var_count)?.fields,
+            TypeKind::Module => {
+                entry.constants = variables(type_info, var_count)?.constants;
+                entry.members = describe_members(type_info)?;
+            }
+            TypeKind::Interface | TypeKind::DispInterface => {
+                entry.members = describe_members(type_info)?;
+                entry.interfaces = implemented(type_info, impl_count)?;
+            }
+            TypeKind::CoClass => entry.interfaces = implemented(type_info, impl_count)?,
+            TypeKind::Alias => {}
+        }
+
+        Ok(entry)
+    }
+
+    /// Variables d'un type, selon leur nature
+    #[derive(Default)]
+    struct Variables {
+        /// Constantes entières (énumérations)
+        values: Vec<EnumValue>,
+        /// Toutes les constantes, avec leur valeur en texte (modules)
+        constants: Vec<ConstantInfo>,
+        fields: Vec<FieldInfo>,
+    }
+
+    /// Constantes (VAR_CONST) et champs (VAR_PERINSTANCE) d'un type
+    ///
+    /// Une constante non entière ou illisible ne fait pas échouer le parcours :
+    /// elle est seulement absente des valeurs d'énumération.
+    fn variables(type_info: &ITypeInfo, count: u16) -> SageResult<Variables> {
+        let mut variables = Variables::default();
+
+        for index in 0..count as u32 {
+            let desc = unsafe { type_info.GetVarDesc(index)? };
+            let (memid, varkind, constant, type_name) = unsafe {
+                let desc = &*desc;
+                let constant = if desc.varkind == VAR_CONST && !desc.Anonymous.lpvarValue.is_null() {
+                    SafeVariant::from_variant(&*desc.Anonymous.lpvarValue).ok()
+                } else {
+                    None
+                };
+                (desc.memid, desc.varkind, constant, type_name(type_info, &desc.elemdescVar.tdesc))
+            };
+            unsafe { type_info.ReleaseVarDesc(desc) };
+
+            let (name, description) = documentation(type_info, memid);
+            match varkind {
+                VAR_CONST => {
+                    let integer = constant.as_ref().filter(|c| !matches!(c, SafeVariant::BStr(_)));
+                    if let Some(value) = integer.and_then(|c| c.to_i32().ok()) {
+                        variables.values.push(EnumValue {
+                            name: name.clone(),
+                            value,
+                            description: description.clone(),
+                        });
+                    }
+                    variables.constants.push(ConstantInfo {
+                        name,
+                        type_name,
+                        value: constant.and_then(|c| c.to_string().ok()),
+                        description,
+                    });
+                }
+                VAR_PERINSTANCE => variables.fields.push(FieldInfo {
+                    name,
+                    type_name,
+                    description,
+                }),
+                _ => {}
+            }
+        }
+
+        Ok(variables)
+    }
+
+    /// Interfaces implémentées (coclasse) ou héritées (interface)
+    fn implemented(type_info: &ITypeInfo, count: u16) -> SageResult<Vec<ImplementedInterface>> {
+        let mut interfaces = Vec::with_capacity(count as usize);
+        for index in 0..count as u32 {
+            let (referenced, flags) = unsafe {
+                let href = type_info.GetRefTypeOfImplType(index)?;
+                (type_info.GetRefTypeInfo(href)?, type_info.GetImplTypeFlags(index)?)
+            };
+            interfaces.push(ImplementedInterface {
+                name: documentation(&referenced, MEMBERID_NIL).0,
+                is_default: flags.0 & IMPLTYPEFLAG_FDEFAULT.0 != 0,
+                is_source: flags.0 & IMPLTYPEFLAG_FSOURCE.0 != 0,
+            });
+        }
+        Ok(interfaces)
+    }
+
+    /// Nom et chaîne d'aide d'un membre, ou du type lui-même avec `MEMBERID_NIL`
+    fn documentation(type_info: &ITypeInfo, memid: i32) -> (String, Option<String>) {
+        let mut name = BSTR::default();
+        let mut doc = BSTR::default();
+        let found = unsafe {
+            type_info.GetDocumentation(memid, Some(&mut name), Some(&mut doc), std::ptr::null_mut(), None)
+        };
+        match found {
+            Ok(()) => (name.to_string(), non_empty(doc)),
+            Err(_) => (format!("member{}", memid), None),
+        }
+    }
+
+    fn non_empty(text: BSTR) -> Option<String> {
+        Some(text.to_string()).filter(|t| !t.is_empty())
+    }
+
+    fn guid_string(guid: &GUID) -> String {
+        format!("{{{:?}}}", guid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::com::{MemberType, ParamInfo};
+
+    fn value(name: &str, value: i32) -> EnumValue {
+        EnumValue { name: name.to_string(), value, description: None }
+    }
+
+    fn entry(name: &str, kind: TypeKind) -> TypeEntry {
+        TypeEntry {
+            name: name.to_string(),
+            guid: "{00000000-0000-0000-0000-000000000000}".to_string(),
+            kind,
+            is_dual: false,
+            is_hidden: false,
+            description: None,
+            members: Vec::new(),
+            values: Vec::new(),
+            constants: Vec::new(),
+            fields: Vec::new(),
+            interfaces: Vec::new(),
+            alias_of: None,
+        }
+    }
+
+    fn library() -> TypeLibrary {
+        let mut sens = entry("EcritureSensType", TypeKind::Enum);
+        sens.values = vec![value("EcritureSensTypeDebit", 0), value("EcritureSensTypeCredit", 1)];
+
+        let mut journal = entry("IBOJournal3", TypeKind::DispInterface);
+        journal.is_dual = true;
+        journal.members = vec![MemberInfo {
+            id: 3,
+            name: "JO_Num".to_string(),
+            member_type: MemberType::PropertyPut,
+            param_count: Some(1),
+            return_type: Some("void".to_string()),
+            params: vec![ParamInfo {
+                name: "value".to_string(),
+                type_name: "string".to_string(),
+                optional: false,
+                default_value: None,
+                is_out: false,
+            }],
+            description: None,
+        }];
+
+        let mut stream = entry("BSCPTAApplication100c", TypeKind::CoClass);
+        stream.interfaces = vec![
+            ImplementedInterface { name: "IEvents".to_string(), is_default: true, is_source: true },
+            ImplementedInterface { name: "IBSCPTAApplication3".to_string(), is_default: true, is_source: false },
+        ];
+
+        // Constante de module non entière : conservée en texte
+        let mut constantes = entry("Constantes", TypeKind::Module);
+        constantes.constants = vec![ConstantInfo {
+            name: "VersionObjets".to_string(),
+            type_name: "string".to_string(),
+            value: Some("11.0".to_string()),
+            description: None,
+        }];
+
+        TypeLibrary {
+            name: "Objets100c".to_string(),
+            guid: "{A1B2C3D4-0000-0000-0000-000000000000}".to_string(),
+            version: SageVersion { major: 11, minor: 0 },
+            description: Some("Objets Métier Sage 100c".to_string()),
+            types: vec![sens, journal, stream, constantes],
+        }
+    }
+
+    #[test]
+    fn test_enum_lookup() {
+        let library = library();
+
+        assert_eq!(library.enum_value("ecrituresenstype", "EcritureSensTypeCredit"), Some(1));
+        assert_eq!(library.find("EcritureSensType").unwrap().value_name(0), Some("EcritureSensTypeDebit"));
+        assert_eq!(library.enum_value("EcritureSensType", "Inconnu"), None);
+        assert_eq!(library.enums().count(), 1);
+        assert_eq!(
+            library.find("BSCPTAApplication100c").unwrap().default_interface(),
+            Some("IBSCPTAApplication3")
+        );
+    }
+
+    #[test]
+    fn test_library_round_trips_through_json() {
+        let json = serde_json::to_string(&library()).unwrap();
+        let restored: TypeLibrary = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.version, SageVersion { major: 11, minor: 0 });
+        let journal = restored.find("IBOJournal3").unwrap();
+        assert!(journal.is_dual);
+        assert_eq!(journal.kind, TypeKind::DispInterface);
+        assert_eq!(journal.members[0].signature(), "JO_Num(value: string)");
+        assert_eq!(restored.find("EcritureSensType").unwrap().values.len(), 2);
+        assert_eq!(restored.find("Constantes").unwrap().constants[0].value.as_deref(), Some("11.0"));
+    }
+}
@@ -1,7 +1,19 @@
-use crate::errors::{SageError, SageResult};
-use windows::{core::*, Win32::{System::{Com::IDispatch, Variant::*}}};
+use super::backend::DispatchObject;
+use super::currency::{self, RoundingMode};
+use super::ole_date;
+use super::out_param::OutParam;
+use super::safe_array::{ArrayData, SafeArray};
+use crate::errors::{HResult, SageError, SageResult};
+use chrono::{NaiveDate, NaiveDateTime};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use std::str::FromStr;
 
 /// Wrapper sûr pour les VARIANT COM
+///
+/// Modèle de valeur indépendant de la plateforme ; les conversions depuis/vers
+/// `VARIANT` sont fournies par la couche COM (feature `com`).
+/// La sérialisation serde conserve le type exact de chaque valeur.
 #[derive(Debug, Clone)]
 #[allow(dead_code)] // Les variantes seront utilisées dans les futures versions
 pub enum SafeVariant {
@@ -13,197 +25,24 @@ pub enum SafeVariant {
     R4(f32),
     R8(f64),
     Currency(i64), // CY
+    Decimal(Decimal), // DECIMAL: montant exact (VT_DECIMAL)
     Date(f64),     // DATE
     BStr(String),
-    Error(HRESULT),
+    Error(HResult),
     I1(i8),
     UI1(u8),
     UI2(u16),
     UI4(u32),
     I8(i64),
     UI8(u64),
-    Dispatch(IDispatch), // NOUVEAU: pour stocker les objets COM
+    Dispatch(DispatchObject), // NOUVEAU: pour stocker les objets d'automation
     Unknown(String),     // NOUVEAU: pour les types non reconnus
+    Array(SafeArray),    // VT_ARRAY: tableaux typés à une ou plusieurs dimensions
+    ByRef(OutParam),     // VT_BYREF: paramètre [in, out] relu après l'appel
+    Missing,             // Paramètre optionnel omis (VT_ERROR / DISP_E_PARAMNOTFOUND)
 }
 
 impl SafeVariant {
-    /// Crée un SafeVariant à partir d'un VARIANT Windows - PRAGMATIQUE v0.1.3
-    pub fn from_variant(variant: VARIANT) -> SageResult<Self> {
-        unsafe {
-            // Obtenir le type de variant
-            let vt = variant.Anonymous.Anonymous.vt;
-            
-            match vt {
-                VT_EMPTY => Ok(SafeVariant::Empty),
-                VT_NULL => Ok(SafeVariant::Null),
-                
-                VT_BSTR => {
-                    // Accès sécurisé au BSTR
-                    let bstr = &variant.Anonymous.Anonymous.Anonymous.bstrVal;
-                    if bstr.is_empty() {
-                        Ok(SafeVariant::BStr(String::new()))
-                    } else {
-                        let rust_string = bstr.to_string();
-                        Ok(SafeVariant::BStr(rust_string))
-                    }
-                },
-                
-                VT_BOOL => {
-                    let val = variant.Anonymous.Anonymous.Anonymous.boolVal;
-                    Ok(SafeVariant::Bool(val.as_bool()))
-                },
-                
-                VT_I2 => {
-                    let val = variant.Anonymous.Anonymous.Anonymous.iVal;
-                    Ok(SafeVariant::I2(val))
-                },
-                
-                VT_I4 => {
-                    let val = variant.Anonymous.Anonymous.Anonymous.lVal;
-                    Ok(SafeVariant::I4(val))
-                },
-                
-                VT_R4 => {
-                    let val = variant.Anonymous.Anonymous.Anonymous.fltVal;
-                    Ok(SafeVariant::R4(val))
-                },
-                
-                VT_R8 => {
-                    let val = variant.Anonymous.Anonymous.Anonymous.dblVal;
-                    Ok(SafeVariant::R8(val))
-                },
-                
-                VT_DISPATCH => {
-                    // Gérer les objets COM IDispatch - CORRIGÉ
-                    let dispatch_opt = &variant.Anonymous.Anonymous.Anonymous.pdispVal;
-                    if let Some(dispatch) = dispatch_opt.as_ref() {
-                        Ok(SafeVariant::Dispatch(dispatch.clone()))
-                    } else {
-                        Ok(SafeVariant::Unknown("IDispatch null".to_string()))
-                    }
-                },
-                
-                VT_UNKNOWN => {
-                    // Gérer les objets COM IUnknown
-                    Ok(SafeVariant::Unknown(format!("IUnknown object (VT_{})", vt.0)))
-                },
-                
-                _ => {
-                    // Pour les types non supportés, retourner une description
-                    Ok(SafeVariant::Unknown(format!("Type VARIANT VT_{} - Conversion en v0.1.4", vt.0)))
-                }
-            }
-        }
-    }
-
-    /// Convertit vers un VARIANT Windows - VERSION FONCTIONNELLE v0.1.3+
-    pub fn to_variant(&self) -> SageResult<VARIANT> {
-        // Solution ultime: Utiliser VariantInit qui retourne directement une VARIANT
-        // puis utiliser la méthode brutale pour contourner ManuallyDrop
-        
-        match self {
-            SafeVariant::Empty => {
-                unsafe { Ok(windows::Win32::System::Variant::VariantInit()) }
-            },
-            
-            SafeVariant::BStr(s) => {
-                let bstr = BSTR::from(s.as_str());
-                
-                unsafe {
-                    let mut variant = windows::Win32::System::Variant::VariantInit();
-                    
-                    // Approche directe avec transmute pour contourner ManuallyDrop
-                    let variant_ptr = &mut variant as *mut VARIANT as *mut u8;
-                    
-                    // Offset vers vt field (généralement à l'offset 0)
-                    let vt_ptr = variant_ptr as *mut u16;
-                    *vt_ptr = VT_BSTR.0;
-                    
-                    // Offset vers le champ bstrVal (généralement après vt + wReserved fields)
-                    let bstr_ptr = variant_ptr.add(8) as *mut BSTR; // 8 = 2 (vt) + 2 (wReserved1) + 2 (wReserved2) + 2 (wReserved3)
-                    *bstr_ptr = bstr;
-                    
-                    Ok(variant)
-                }
-            },
-            
-            SafeVariant::Bool(val) => {
-                unsafe {
-                    let mut variant = windows::Win32::System::Variant::VariantInit();
-                    
-                    let variant_ptr = &mut variant as *mut VARIANT as *mut u8;
-                    let vt_ptr = variant_ptr as *mut u16;
-                    *vt_ptr = VT_BOOL.0;
-                    
-                    let bool_ptr = variant_ptr.add(8) as *mut i16;
-                    *bool_ptr = if *val { -1 } else { 0 }; // VARIANT_BOOL convention
-                    
-                    Ok(variant)
-                }
-            },
-            
-            SafeVariant::I4(val) => {
-                unsafe {
-                    let mut variant = windows::Win32::System::Variant::VariantInit();
-                    
-                    let variant_ptr = &mut variant as *mut VARIANT as *mut u8;
-                    let vt_ptr = variant_ptr as *mut u16;
-                    *vt_ptr = VT_I4.0;
-                    
-                    let i4_ptr = variant_ptr.add(8) as *mut i32;
-                    *i4_ptr = *val;
-                    
-                    Ok(variant)
-                }
-            },
-            
-            SafeVariant::R8(val) => {
-                unsafe {
-                    let mut variant = windows::Win32::System::Variant::VariantInit();
-                    
-                    let variant_ptr = &mut variant as *mut VARIANT as *mut u8;
-                    let vt_ptr = variant_ptr as *mut u16;
-                    *vt_ptr = VT_R8.0;
-                    
-                    let r8_ptr = variant_ptr.add(8) as *mut f64;
-                    *r8_ptr = *val;
-                    
-                    Ok(variant)
-                }
-            },
-            
-            SafeVariant::Dispatch(dispatch) => {
-                unsafe {
-                    let mut variant = windows::Win32::System::Variant::VariantInit();
-                    
-                    let variant_ptr = &mut variant as *mut VARIANT as *mut u8;
-                    let vt_ptr = variant_ptr as *mut u16;
-                    *vt_ptr = VT_DISPATCH.0;
-                    
-                    let dispatch_ptr = variant_ptr.add(8) as *mut Option<IDispatch>;
-                    *dispatch_ptr = Some(dispatch.clone());
-                    
-                    Ok(variant)
-                }
-            },
-            
-            // Conversions automatiques
-            SafeVariant::I2(val) => {
-                SafeVariant::I4(*val as i32).to_variant()
-            },
-            
-            SafeVariant::R4(val) => {
-                SafeVariant::R8(*val as f64).to_variant()
-            },
-            
-            _ => {
-                // Pour les autres types, retourner VT_EMPTY
-                println!("VARIANT conversion: Type non implémenté: {:?}", self);
-                unsafe { Ok(windows::Win32::System::Variant::VariantInit()) }
-            }
-        }
-    }
-
     /// Convertit vers String si possible - AMÉLIORÉE v0.1.3
     pub fn to_string(&self) -> SageResult<String> {
         match self {
@@ -218,18 +57,16 @@ impl SafeVariant {
             SafeVariant::R8(f) => Ok(f.to_string()),
             SafeVariant::Bool(b) => Ok(b.to_string()),
             SafeVariant::Currency(c) => {
-                // Conversion currency vers decimal (diviser par 10000)
-                let decimal_val = *c as f64 / 10000.0;
-                Ok(format!("{:.4}", decimal_val))
+                // Conversion currency vers decimal exact (4 décimales)
+                Ok(currency::currency_to_decimal(*c).to_string())
             },
+            SafeVariant::Decimal(d) => Ok(d.to_string()),
             SafeVariant::Date(d) => {
-                // Conversion DATE COM vers string lisible
+                // Conversion DATE COM vers string lisible (ISO 8601)
                 // DATE COM = nombre de jours depuis 30/12/1899
-                let days_since_epoch = *d;
-                if days_since_epoch == 0.0 {
-                    Ok("1899-12-30T00:00:00".to_string())
-                } else {
-                    Ok(format!("DATE({})", days_since_epoch))
+                match ole_date::ole_date_to_naive_datetime(*d) {
+                    Ok(datetime) => Ok(datetime.format("%Y-%m-%dT%H:%M:%S").to_string()),
+                    Err(_) => Ok(format!("DATE({})", d)),
                 }
             },
             SafeVariant::Dispatch(_) => Ok("IDispatch object".to_string()),
@@ -277,6 +114,11 @@ impl SafeVariant {
             SafeVariant::R4(f) => Ok(*f as i32),
             SafeVariant::R8(f) => Ok(*f as i32),
             SafeVariant::Currency(c) => Ok((*c / 10000) as i32),
+            SafeVariant::Decimal(d) => d.trunc().to_i32().ok_or_else(|| SageError::ConversionError {
+                from_type: "Decimal".to_string(),
+                to_type: "i32".to_string(),
+                value: format!("Valeur {} hors limites i32", d),
+            }),
             SafeVariant::Bool(b) => Ok(if *b { 1 } else { 0 }),
             SafeVariant::BStr(s) => s.parse::<i32>().map_err(|_| SageError::ConversionError {
                 from_type: "String".to_string(),
@@ -303,7 +145,8 @@ impl SafeVariant {
             SafeVariant::UI2(i) => Ok(*i as f64),
             SafeVariant::UI4(i) => Ok(*i as f64),
             SafeVariant::UI8(i) => Ok(*i as f64),
-            SafeVariant::Currency(c) => Ok(*c as f64 / 10000.0),
+            SafeVariant::Currency(c) => Ok(currency::currency_to_decimal(*c).to_f64().unwrap_or_default()),
+            SafeVariant::Decimal(d) => Ok(d.to_f64().unwrap_or_default()),
             SafeVariant::Date(d) => Ok(*d),
             SafeVariant::Bool(b) => Ok(if *b { 1.0 } else { 0.0 }),
             SafeVariant::BStr(s) => s.parse::<f64>().map_err(|_| SageError::ConversionError {
@@ -333,6 +176,7 @@ impl SafeVariant {
             SafeVariant::R4(f) => Ok(*f != 0.0),
             SafeVariant::R8(f) => Ok(*f != 0.0),
             SafeVariant::Currency(c) => Ok(*c != 0),
+            SafeVariant::Decimal(d) => Ok(!d.is_zero()),
             SafeVariant::Empty => Ok(false),
             SafeVariant::Null => Ok(false),
             SafeVariant::BStr(s) => match s.to_lowercase().as_str() {
@@ -353,9 +197,11 @@ impl SafeVariant {
     }
 
     /// Convertit vers Currency (format Sage) - NOUVEAU v0.1.3
+    ///
+    /// Pour les montants comptables, préférer `to_decimal()` qui est exact.
     pub fn to_currency(&self) -> SageResult<f64> {
         match self {
-            SafeVariant::Currency(c) => Ok(*c as f64 / 10000.0),
+            SafeVariant::Currency(_) | SafeVariant::Decimal(_) => self.to_f64(),
             SafeVariant::R4(f) => Ok(*f as f64),
             SafeVariant::R8(f) => Ok(*f),
             SafeVariant::I2(i) => Ok(*i as f64),
@@ -374,6 +220,57 @@ impl SafeVariant {
         }
     }
 
+    /// Convertit vers un décimal exact (montants Currency et VT_DECIMAL)
+    pub fn to_decimal(&self) -> SageResult<Decimal> {
+        let error = || SageError::ConversionError {
+            from_type: self.type_name().to_string(),
+            to_type: "Decimal".to_string(),
+            value: format!("{:?}", self),
+        };
+
+        match self {
+            SafeVariant::Decimal(d) => Ok(*d),
+            SafeVariant::Currency(c) => Ok(currency::currency_to_decimal(*c)),
+            SafeVariant::I1(i) => Ok(Decimal::from(*i)),
+            SafeVariant::UI1(i) => Ok(Decimal::from(*i)),
+            SafeVariant::I2(i) => Ok(Decimal::from(*i)),
+            SafeVariant::UI2(i) => Ok(Decimal::from(*i)),
+            SafeVariant::I4(i) => Ok(Decimal::from(*i)),
+            SafeVariant::UI4(i) => Ok(Decimal::from(*i)),
+            SafeVariant::I8(i) => Ok(Decimal::from(*i)),
+            SafeVariant::UI8(i) => Ok(Decimal::from(*i)),
+            // Les flottants sont convertis au plus court (0.1 donne 0.1, pas 0.1000000000000000055...)
+            SafeVariant::R4(f) => Decimal::from_str(&f.to_string()).map_err(|_| error()),
+            SafeVariant::R8(f) => Decimal::from_str(&f.to_string()).map_err(|_| error()),
+            SafeVariant::BStr(s) => Decimal::from_str(s.trim()).map_err(|_| error()),
+            _ => Err(error()),
+        }
+    }
+
+    /// Convertit une DATE COM en date/heure
+    pub fn to_naive_datetime(&self) -> SageResult<NaiveDateTime> {
+        match self {
+            SafeVariant::Date(d) => ole_date::ole_date_to_naive_datetime(*d),
+            SafeVariant::BStr(s) => NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S")
+                .or_else(|_| NaiveDate::parse_from_str(s, "%Y-%m-%d").map(|d| d.and_time(Default::default())))
+                .map_err(|_| SageError::ConversionError {
+                    from_type: "String".to_string(),
+                    to_type: "NaiveDateTime".to_string(),
+                    value: s.clone(),
+                }),
+            _ => Err(SageError::ConversionError {
+                from_type: self.type_name().to_string(),
+                to_type: "NaiveDateTime".to_string(),
+                value: format!("{:?}", self),
+            }),
+        }
+    }
+
+    /// Convertit une DATE COM en date calendaire (l'heure est ignorée)
+    pub fn to_naive_date(&self) -> SageResult<NaiveDate> {
+        self.to_naive_datetime().map(|datetime| datetime.date())
+    }
+
     /// Retourne le nom du type
     pub fn type_name(&self) -> &'static str {
         match self {
@@ -385,6 +282,7 @@ impl SafeVariant {
             SafeVariant::R4(_) => "R4",
             SafeVariant::R8(_) => "R8",
             SafeVariant::Currency(_) => "Currency",
+            SafeVariant::Decimal(_) => "Decimal",
             SafeVariant::Date(_) => "Date",
             SafeVariant::BStr(_) => "BStr",
             SafeVariant::Error(_) => "Error",
@@ -396,6 +294,9 @@ impl SafeVariant {
             SafeVariant::UI8(_) => "UI8",
             SafeVariant::Dispatch(_) => "IDispatch",
             SafeVariant::Unknown(_) => "Unknown",
+            SafeVariant::Array(_) => "Array",
+            SafeVariant::ByRef(_) => "ByRef",
+            SafeVariant::Missing => "Missing",
         }
     }
 
@@ -405,27 +306,54 @@ impl SafeVariant {
         matches!(self, SafeVariant::Empty | SafeVariant::Null)
     }
 
-    /// Convertit le VARIANT en interface IDispatch si possible - CORRIGÉ v0.1.3
-    pub fn to_dispatch(&self) -> SageResult<IDispatch> {
+    /// Convertit le VARIANT en objet d'automation, quel que soit son backend
+    pub fn to_object(&self) -> SageResult<DispatchObject> {
         match self {
-            SafeVariant::Dispatch(dispatch) => {
-                // Cloner l'interface pour éviter les problèmes de durée de vie
-                Ok(dispatch.clone())
-            },
-            SafeVariant::Unknown(desc) if desc.contains("IDispatch") => {
-                Err(SageError::ConversionError {
-                    from_type: "Unknown".to_string(),
-                    to_type: "IDispatch".to_string(),
-                    value: "IDispatch null détecté".to_string(),
-                })
+            SafeVariant::Dispatch(object) => Ok(object.clone()),
+            _ => Err(SageError::ConversionError {
+                from_type: self.type_name().to_string(),
+                to_type: "IDispatch".to_string(),
+                value: format!("Type {} ne peut pas être converti en IDispatch", self.type_name()),
+            }),
+        }
+    }
+
+    /// Accède au tableau contenu dans le VARIANT
+    pub fn to_array(&self) -> SageResult<&SafeArray> {
+        match self {
+            SafeVariant::Array(array) => Ok(array),
+            _ => Err(SageError::ConversionError {
+                from_type: self.type_name().to_string(),
+                to_type: "Array".to_string(),
+                value: format!("{:?}", self),
+            }),
+        }
+    }
+
+    /// Convertit un tableau d'octets (VT_ARRAY|VT_UI1) en blob binaire
+    pub fn to_bytes(&self) -> SageResult<Vec<u8>> {
+        match self {
+            SafeVariant::Array(array) => match array.data() {
+                ArrayData::UI1(bytes) => Ok(bytes.clone()),
+                other => Err(SageError::ConversionError {
+                    from_type: format!("Array<{}>", other.element_type_name()),
+                    to_type: "Vec<u8>".to_string(),
+                    value: format!("{} élément(s)", other.len()),
+                }),
             },
-            _ => {
-                Err(SageError::ConversionError {
-                    from_type: self.type_name().to_string(),
-                    to_type: "IDispatch".to_string(),
-                    value: format!("Type {} ne peut pas être converti en IDispatch", self.type_name()),
-                })
-            }
+            _ => Err(SageError::ConversionError {
+                from_type: self.type_name().to_string(),
+                to_type: "Vec<u8>".to_string(),
+                value: format!("{:?}", self),
+            }),
+        }
+    }
+
+    /// Accède au slot d'un paramètre passé par référence
+    pub fn as_out_param(&self) -> Option<&OutParam> {
+        match self {
+            SafeVariant::ByRef(slot) => Some(slot),
+            _ => None,
         }
     }
 
@@ -458,8 +386,20 @@ impl SafeVariant {
     
     /// Crée un SafeVariant Currency (pour les montants Sage)
     pub fn from_currency(val: f64) -> Self {
-        // Conversion en currency (10000 unités = 1.0)
-        SafeVariant::Currency((val * 10000.0) as i64)
+        // Conversion en currency (10000 unités = 1.0), arrondie et non tronquée
+        SafeVariant::Currency((val * 10000.0).round() as i64)
+    }
+
+    /// Crée un SafeVariant Decimal (VT_DECIMAL) exact
+    pub fn from_decimal(val: Decimal) -> Self {
+        SafeVariant::Decimal(val)
+    }
+
+    /// Crée un SafeVariant Currency exact à partir d'un décimal
+    ///
+    /// Les décimales au-delà de la 4e sont arrondies selon `mode`.
+    pub fn currency_from_decimal(val: Decimal, mode: RoundingMode) -> SageResult<Self> {
+        Ok(SafeVariant::Currency(currency::decimal_to_currency(val, mode)?))
     }
     
     /// Crée un SafeVariant Date à partir d'un timestamp f64
@@ -467,6 +407,16 @@ impl SafeVariant {
         SafeVariant::Date(val)
     }
     
+    /// Crée un SafeVariant Date à partir d'une date/heure
+    pub fn from_naive_datetime(val: NaiveDateTime) -> SageResult<Self> {
+        Ok(SafeVariant::Date(ole_date::naive_datetime_to_ole_date(val)?))
+    }
+
+    /// Crée un SafeVariant Date à minuit du jour donné
+    pub fn from_naive_date(val: NaiveDate) -> SageResult<Self> {
+        Self::from_naive_datetime(val.and_time(Default::default()))
+    }
+
     /// Crée un SafeVariant vide
     pub fn empty() -> Self {
         SafeVariant::Empty
@@ -477,10 +427,11 @@ impl SafeVariant {
         SafeVariant::Null
     }
 
-    /// Crée un SafeVariant à partir d'un IDispatch
-    pub fn from_dispatch(dispatch: IDispatch) -> Self {
-        SafeVariant::Dispatch(dispatch)
+    /// Crée un paramètre optionnel omis, équivalent du `Missing` VB
+    pub fn missing() -> Self {
+        SafeVariant::Missing
     }
+
 }
 
 // Implémentations pratiques pour créer des SafeVariant
@@ -514,9 +465,66 @@ impl From<bool> for SafeVariant {
     }
 }
 
-impl From<IDispatch> for SafeVariant {
-    fn from(dispatch: IDispatch) -> Self {
-        SafeVariant::Dispatch(dispatch)
+impl From<SafeArray> for SafeVariant {
+    fn from(array: SafeArray) -> Self {
+        SafeVariant::Array(array)
+    }
+}
+
+impl From<Vec<SafeVariant>> for SafeVariant {
+    fn from(values: Vec<SafeVariant>) -> Self {
+        SafeVariant::Array(SafeArray::from_data(ArrayData::Variant(values)))
+    }
+}
+
+impl From<Vec<String>> for SafeVariant {
+    fn from(values: Vec<String>) -> Self {
+        SafeVariant::Array(SafeArray::from_data(ArrayData::BStr(values)))
+    }
+}
+
+impl From<Vec<i32>> for SafeVariant {
+    fn from(values: Vec<i32>) -> Self {
+        SafeVariant::Array(SafeArray::from_data(ArrayData::I4(values)))
+    }
+}
+
+impl From<Vec<f64>> for SafeVariant {
+    fn from(values: Vec<f64>) -> Self {
+        SafeVariant::Array(SafeArray::from_data(ArrayData::R8(values)))
+    }
+}
+
+impl From<Vec<u8>> for SafeVariant {
+    fn from(bytes: Vec<u8>) -> Self {
+        SafeVariant::Array(SafeArray::from_data(ArrayData::UI1(bytes)))
+    }
+}
+
+impl From<Decimal> for SafeVariant {
+    fn from(val: Decimal) -> Self {
+        SafeVariant::Decimal(val)
+    }
+}
+
+/// Toute date de l'an 100 à l'an 9999 est représentable ; au-delà la
+/// conversion échoue plutôt que de transmettre une date bornée à Sage.
+///
+/// C'est pour cette raison que la conversion est un `TryFrom` et non le
+/// `From<NaiveDate>` initialement prévu : `NaiveDate` couvre des années que
+/// VT_DATE ne sait pas représenter, et un `From` devrait soit paniquer, soit
+/// borner la date en silence.
+impl TryFrom<NaiveDate> for SafeVariant {
+    type Error = SageError;
+
+    fn try_from(val: NaiveDate) -> SageResult<Self> {
+        SafeVariant::from_naive_date(val)
+    }
+}
+
+impl From<DispatchObject> for SafeVariant {
+    fn from(object: DispatchObject) -> Self {
+        SafeVariant::Dispatch(object)
     }
 }
 
@@ -540,7 +548,44 @@ mod tests {
     #[test]
     fn test_bool_conversion() {
         let variant = SafeVariant::from(true);
-        assert_eq!(variant.to_bool().unwrap(), true);
+        assert!(variant.to_bool().unwrap());
+    }
+
+    #[test]
+    fn test_decimal_conversion() {
+        let amount = SafeVariant::Currency(1_234_500);
+        assert_eq!(amount.to_string().unwrap(), "123.4500");
+        assert_eq!(amount.to_decimal().unwrap(), Decimal::new(12345, 2));
+
+        // 0.1 + 0.2 ne doit pas dériver comme en f64
+        let sum = SafeVariant::from(0.1).to_decimal().unwrap() + SafeVariant::from(0.2).to_decimal().unwrap();
+        let variant = SafeVariant::currency_from_decimal(sum, RoundingMode::Bankers).unwrap();
+        assert!(matches!(variant, SafeVariant::Currency(3000)));
+        assert_eq!(SafeVariant::from_currency(0.29).to_decimal().unwrap().to_string(), "0.2900");
+    }
+
+    #[test]
+    fn test_date_conversion() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 31).unwrap();
+        let variant = SafeVariant::try_from(date).unwrap();
+
+        assert!(matches!(variant, SafeVariant::Date(d) if d == 45382.0));
+        assert_eq!(variant.to_naive_date().unwrap(), date);
+        assert_eq!(variant.to_string().unwrap(), "2024-03-31T00:00:00");
+        assert_eq!(SafeVariant::Date(-1.5).to_string().unwrap(), "1899-12-29T12:00:00");
+
+        // Hors de la plage OLE : erreur, pas de date bornée
+        let error = SafeVariant::try_from(NaiveDate::from_ymd_opt(10000, 1, 1).unwrap()).unwrap_err();
+        assert!(matches!(error, SageError::ConversionError { .. }));
+        assert!(SafeVariant::try_from(NaiveDate::from_ymd_opt(99, 12, 31).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_byte_blob_array() {
+        let variant = SafeVariant::from(vec![0x25u8, 0x50, 0x44, 0x46]);
+        assert_eq!(variant.type_name(), "Array");
+        assert_eq!(variant.to_bytes().unwrap(), b"%PDF".to_vec());
+        assert!(SafeVariant::from(vec![1, 2]).to_bytes().is_err());
     }
 
     #[test]
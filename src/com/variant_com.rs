@@ -0,0 +1,475 @@
+use super::backend::DispatchObject;
+use super::{ArrayBound, ArrayData, OwnedVariant, SafeArray, SafeVariant};
+use crate::errors::{HResult, SageError, SageResult};
+use std::mem::ManuallyDrop;
+use rust_decimal::Decimal;
+use windows::Win32::Foundation::{DISP_E_PARAMNOTFOUND, DECIMAL, DECIMAL_0, DECIMAL_0_0, DECIMAL_1, VARIANT_BOOL};
+use windows::{core::*, Win32::{System::{Com::{CY, IDispatch, SAFEARRAY, SAFEARRAYBOUND}, Ole::*, Variant::*}}};
+
+/// Conversions SafeVariant <-> VARIANT, propres à la couche COM
+impl SafeVariant {
+    /// Crée un SafeVariant à partir d'un VARIANT Windows - PRAGMATIQUE v0.1.3
+    ///
+    /// Le VARIANT est seulement lu : son propriétaire reste chargé de le
+    /// libérer (voir `OwnedVariant`).
+    pub fn from_variant(variant: &VARIANT) -> SageResult<Self> {
+        unsafe {
+            // Obtenir le type de variant
+            let vt = variant.Anonymous.Anonymous.vt;
+            
+            match vt {
+                VT_EMPTY => Ok(SafeVariant::Empty),
+                VT_NULL => Ok(SafeVariant::Null),
+                
+                VT_BSTR => {
+                    // Accès sécurisé au BSTR
+                    let bstr = &variant.Anonymous.Anonymous.Anonymous.bstrVal;
+                    if bstr.is_empty() {
+                        Ok(SafeVariant::BStr(String::new()))
+                    } else {
+                        let rust_string = bstr.to_string();
+                        Ok(SafeVariant::BStr(rust_string))
+                    }
+                },
+                
+                VT_BOOL => {
+                    let val = variant.Anonymous.Anonymous.Anonymous.boolVal;
+                    Ok(SafeVariant::Bool(val.as_bool()))
+                },
+                
+                VT_I2 => {
+                    let val = variant.Anonymous.Anonymous.Anonymous.iVal;
+                    Ok(SafeVariant::I2(val))
+                },
+                
+                VT_I4 => {
+                    let val = variant.Anonymous.Anonymous.Anonymous.lVal;
+                    Ok(SafeVariant::I4(val))
+                },
+                
+                VT_I1 => Ok(SafeVariant::I1(variant.Anonymous.Anonymous.Anonymous.cVal as i8)),
+                VT_UI1 => Ok(SafeVariant::UI1(variant.Anonymous.Anonymous.Anonymous.bVal)),
+                VT_UI2 => Ok(SafeVariant::UI2(variant.Anonymous.Anonymous.Anonymous.uiVal)),
+                VT_UI4 => Ok(SafeVariant::UI4(variant.Anonymous.Anonymous.Anonymous.ulVal)),
+                VT_I8 => Ok(SafeVariant::I8(variant.Anonymous.Anonymous.Anonymous.llVal)),
+                VT_UI8 => Ok(SafeVariant::UI8(variant.Anonymous.Anonymous.Anonymous.ullVal)),
+                VT_INT => Ok(SafeVariant::I4(variant.Anonymous.Anonymous.Anonymous.intVal)),
+                VT_UINT => Ok(SafeVariant::UI4(variant.Anonymous.Anonymous.Anonymous.uintVal)),
+                
+                VT_ERROR => {
+                    let scode = variant.Anonymous.Anonymous.Anonymous.scode;
+                    if scode == DISP_E_PARAMNOTFOUND.0 {
+                        Ok(SafeVariant::Missing)
+                    } else {
+                        Ok(SafeVariant::Error(HResult(scode)))
+                    }
+                },
+                
+                VT_R4 => {
+                    let val = variant.Anonymous.Anonymous.Anonymous.fltVal;
+                    Ok(SafeVariant::R4(val))
+                },
+                
+                VT_R8 => {
+                    let val = variant.Anonymous.Anonymous.Anonymous.dblVal;
+                    Ok(SafeVariant::R8(val))
+                },
+                
+                VT_DATE => {
+                    let val = variant.Anonymous.Anonymous.Anonymous.date;
+                    Ok(SafeVariant::Date(val))
+                },
+                
+                VT_CY => {
+                    // Montant en dix-millièmes, conservé tel quel (exact)
+                    let val = variant.Anonymous.Anonymous.Anonymous.cyVal.int64;
+                    Ok(SafeVariant::Currency(val))
+                },
+                
+                VT_DECIMAL => {
+                    // Le DECIMAL occupe tout le VARIANT (vt est dans wReserved)
+                    let dec = variant.Anonymous.decVal;
+                    let lo64 = dec.Anonymous2.Lo64;
+                    let sign_scale = dec.Anonymous1.Anonymous;
+                    Ok(SafeVariant::Decimal(Decimal::from_parts(
+                        lo64 as u32,
+                        (lo64 >> 32) as u32,
+                        dec.Hi32,
+                        sign_scale.sign & 0x80 != 0,
+                        sign_scale.scale as u32,
+                    )))
+                },
+                
+                VT_DISPATCH => {
+                    // Gérer les objets COM IDispatch - CORRIGÉ
+                    let dispatch_opt = &variant.Anonymous.Anonymous.Anonymous.pdispVal;
+                    if let Some(dispatch) = dispatch_opt.as_ref() {
+                        Ok(SafeVariant::Dispatch(DispatchObject::new(dispatch.clone())))
+                    } else {
+                        Ok(SafeVariant::Unknown("IDispatch null".to_string()))
+                    }
+                },
+                
+                VT_UNKNOWN => {
+                    // Un IUnknown qui expose IDispatch reste utilisable comme objet
+                    let unknown_opt = &variant.Anonymous.Anonymous.Anonymous.punkVal;
+                    match unknown_opt.as_ref().map(|unknown| unknown.cast::<IDispatch>()) {
+                        Some(Ok(dispatch)) => Ok(SafeVariant::Dispatch(DispatchObject::new(dispatch))),
+                        Some(Err(_)) => Ok(SafeVariant::Unknown(format!("IUnknown object (VT_{})", vt.0))),
+                        None => Ok(SafeVariant::Unknown("IUnknown null".to_string())),
+                    }
+                },
+                
+                vt if vt == VARENUM(VT_BYREF.0 | VT_VARIANT.0) => {
+                    // VARIANT passé par référence : lire la valeur pointée
+                    let pvar = variant.Anonymous.Anonymous.Anonymous.pvarVal;
+                    if pvar.is_null() {
+                        Ok(SafeVariant::Null)
+                    } else {
+                        SafeVariant::from_variant(&*pvar)
+                    }
+                },
+                
+                vt if vt.0 & VT_ARRAY.0 != 0 => {
+                    // Tableau SAFEARRAY, éventuellement passé par référence
+                    let parray = if vt.0 & VT_BYREF.0 != 0 {
+                        let pparray = variant.Anonymous.Anonymous.Anonymous.pparray;
+                        if pparray.is_null() { std::ptr::null_mut() } else { *pparray }
+                    } else {
+                        variant.Anonymous.Anonymous.Anonymous.parray
+                    };
+                    read_safe_array(parray, VARENUM(vt.0 & VT_TYPEMASK.0))
+                },
+                
+                _ => {
+                    // Pour les types non supportés, retourner une description
+                    Ok(SafeVariant::Unknown(format!("Type VARIANT VT_{} non supporté", vt.0)))
+                }
+            }
+        }
+    }
+
+    /// Convertit vers un VARIANT Windows
+    ///
+    /// Chaque type remplit le champ correspondant de l'union ; un type qui n'a
+    /// pas d'équivalent VARIANT retourne une `ConversionError` plutôt qu'un
+    /// VT_EMPTY, que Sage interpréterait comme un paramètre omis.
+    pub fn to_variant(&self) -> SageResult<OwnedVariant> {
+        let variant = match self {
+            SafeVariant::Empty => VARIANT::default(),
+            SafeVariant::Null => new_variant(VT_NULL, |_| {}),
+            SafeVariant::Bool(val) => new_variant(VT_BOOL, |u| u.boolVal = VARIANT_BOOL::from(*val)),
+            SafeVariant::I1(val) => new_variant(VT_I1, |u| u.cVal = *val as u8),
+            SafeVariant::UI1(val) => new_variant(VT_UI1, |u| u.bVal = *val),
+            SafeVariant::I2(val) => new_variant(VT_I2, |u| u.iVal = *val),
+            SafeVariant::UI2(val) => new_variant(VT_UI2, |u| u.uiVal = *val),
+            SafeVariant::I4(val) => new_variant(VT_I4, |u| u.lVal = *val),
+            SafeVariant::UI4(val) => new_variant(VT_UI4, |u| u.ulVal = *val),
+            SafeVariant::I8(val) => new_variant(VT_I8, |u| u.llVal = *val),
+            SafeVariant::UI8(val) => new_variant(VT_UI8, |u| u.ullVal = *val),
+            SafeVariant::R4(val) => new_variant(VT_R4, |u| u.fltVal = *val),
+            SafeVariant::R8(val) => new_variant(VT_R8, |u| u.dblVal = *val),
+            SafeVariant::Date(val) => new_variant(VT_DATE, |u| u.date = *val),
+            SafeVariant::Currency(val) => new_variant(VT_CY, |u| u.cyVal = CY { int64: *val }),
+            SafeVariant::Error(hr) => new_variant(VT_ERROR, |u| u.scode = hr.0),
+            SafeVariant::Missing => new_variant(VT_ERROR, |u| u.scode = DISP_E_PARAMNOTFOUND.0),
+
+            SafeVariant::BStr(s) => {
+                let bstr = BSTR::from(s.as_str());
+                new_variant(VT_BSTR, |u| u.bstrVal = ManuallyDrop::new(bstr))
+            },
+
+            SafeVariant::Decimal(val) => {
+                let mantissa = val.mantissa().unsigned_abs();
+                let decimal = DECIMAL {
+                    wReserved: 0,
+                    Anonymous1: DECIMAL_0 {
+                        Anonymous: DECIMAL_0_0 {
+                            scale: val.scale() as u8,
+                            sign: if val.is_sign_negative() { 0x80 } else { 0 },
+                        },
+                    },
+                    Hi32: (mantissa >> 64) as u32,
+                    Anonymous2: DECIMAL_1 { Lo64: mantissa as u64 },
+                };
+
+                let mut variant = VARIANT::default();
+                variant.Anonymous.decVal = decimal;
+                // Écrire vt après le DECIMAL, qui partage son premier mot
+                unsafe { (*variant.Anonymous.Anonymous).vt = VT_DECIMAL };
+                variant
+            },
+
+            SafeVariant::Dispatch(object) => {
+                // Seuls les objets COM réels peuvent être transmis à IDispatch::Invoke
+                let dispatch = object.downcast_ref::<IDispatch>().ok_or_else(|| {
+                    SageError::ConversionError {
+                        from_type: "IDispatch".to_string(),
+                        to_type: "VARIANT".to_string(),
+                        value: "Objet d'automation non COM".to_string(),
+                    }
+                })?;
+                let dispatch = dispatch.clone();
+                new_variant(VT_DISPATCH, |u| u.pdispVal = ManuallyDrop::new(Some(dispatch)))
+            },
+
+            SafeVariant::Array(array) => {
+                let (parray, element_vt) = create_safe_array(array)?;
+                new_variant(VARENUM(VT_ARRAY.0 | element_vt.0), |u| u.parray = parray)
+            },
+
+            // Un Unknown ne conserve que la description de l'objet d'origine
+            SafeVariant::Unknown(desc) => {
+                return Err(SageError::ConversionError {
+                    from_type: "Unknown".to_string(),
+                    to_type: "VARIANT".to_string(),
+                    value: format!("Objet non transmissible (description seule): {}", desc),
+                });
+            },
+
+            // Les VT_BYREF sont construits par Invoke autour d'un VARIANT dédié
+            SafeVariant::ByRef(_) => {
+                return Err(SageError::ConversionError {
+                    from_type: "ByRef".to_string(),
+                    to_type: "VARIANT".to_string(),
+                    value: "Un OutParam ne peut être passé que comme argument d'appel".to_string(),
+                });
+            },
+        };
+
+        // SAFETY: VARIANT construit ci-dessus, dont on est l'unique propriétaire
+        Ok(unsafe { OwnedVariant::from_raw(variant) })
+    }
+
+    /// Convertit le VARIANT en interface IDispatch si possible - CORRIGÉ v0.1.3
+    pub fn to_dispatch(&self) -> SageResult<IDispatch> {
+        match self {
+            SafeVariant::Dispatch(object) => {
+                // Cloner l'interface pour éviter les problèmes de durée de vie
+                object.downcast_ref::<IDispatch>().cloned().ok_or_else(|| {
+                    SageError::ConversionError {
+                        from_type: "IDispatch".to_string(),
+                        to_type: "IDispatch".to_string(),
+                        value: "Objet d'automation non COM".to_string(),
+                    }
+                })
+            },
+            SafeVariant::Unknown(desc) if desc.contains("IDispatch") => {
+                Err(SageError::ConversionError {
+                    from_type: "Unknown".to_string(),
+                    to_type: "IDispatch".to_string(),
+                    value: "IDispatch null détecté".to_string(),
+                })
+            },
+            _ => {
+                Err(SageError::ConversionError {
+                    from_type: self.type_name().to_string(),
+                    to_type: "IDispatch".to_string(),
+                    value: format!("Type {} ne peut pas être converti en IDispatch", self.type_name()),
+                })
+            }
+        }
+    }
+
+    /// Crée un SafeVariant à partir d'un IDispatch
+    pub fn from_dispatch(dispatch: IDispatch) -> Self {
+        SafeVariant::Dispatch(DispatchObject::new(dispatch))
+    }
+}
+
+impl From<IDispatch> for SafeVariant {
+    fn from(dispatch: IDispatch) -> Self {
+        SafeVariant::Dispatch(DispatchObject::new(dispatch))
+    }
+}
+
+/// Crée un VARIANT du type `vt` dont le champ de l'union est rempli par `fill`
+fn new_variant(vt: VARENUM, fill: impl FnOnce(&mut VARIANT_0_0_0)) -> VARIANT {
+    let mut variant = VARIANT::default();
+    // SAFETY: VARIANT::default() est zéroé, la vue VARIANT_0_0 est donc valide
+    let inner = unsafe { &mut *variant.Anonymous.Anonymous };
+    inner.vt = vt;
+    fill(&mut inner.Anonymous);
+    variant
+}
+
+/// Lit un SAFEARRAY COM (toutes dimensions) dans un `SafeArray`
+unsafe fn read_safe_array(parray: *const SAFEARRAY, element_vt: VARENUM) -> SageResult<SafeVariant> {
+    if parray.is_null() {
+        // Tableau dynamique non dimensionné côté VB
+        return Ok(SafeVariant::Null);
+    }
+
+    if !matches!(element_vt, VT_VARIANT | VT_BSTR | VT_I4 | VT_R8 | VT_UI1) {
+        return Ok(SafeVariant::Unknown(format!(
+            "Tableau d'éléments VT_{} non supporté",
+            element_vt.0
+        )));
+    }
+
+    unsafe {
+        let dims = SafeArrayGetDim(parray);
+        let mut bounds = Vec::with_capacity(dims as usize);
+        for dim in 1..=dims {
+            let lower = SafeArrayGetLBound(parray, dim)?;
+            let upper = SafeArrayGetUBound(parray, dim)?;
+            bounds.push(ArrayBound::new(lower, (upper - lower + 1).max(0) as u32));
+        }
+        let count: usize = bounds.iter().map(|b| b.len as usize).product();
+
+        let data = if count == 0 {
+            Ok(empty_array_data(element_vt))
+        } else {
+            let mut raw = std::ptr::null_mut();
+            SafeArrayAccessData(parray, &mut raw)?;
+            let data = read_elements(raw, count, element_vt);
+            SafeArrayUnaccessData(parray)?;
+            data
+        }?;
+
+        Ok(SafeVariant::Array(SafeArray::new(bounds, data)?))
+    }
+}
+
+/// Données vides du type d'élément donné
+fn empty_array_data(element_vt: VARENUM) -> ArrayData {
+    match element_vt {
+        VT_BSTR => ArrayData::BStr(Vec::new()),
+        VT_I4 => ArrayData::I4(Vec::new()),
+        VT_R8 => ArrayData::R8(Vec::new()),
+        VT_UI1 => ArrayData::UI1(Vec::new()),
+        _ => ArrayData::Variant(Vec::new()),
+    }
+}
+
+/// Copie les éléments d'un SAFEARRAY verrouillé par SafeArrayAccessData
+unsafe fn read_elements(raw: *const std::ffi::c_void, count: usize, element_vt: VARENUM) -> SageResult<ArrayData> {
+    unsafe {
+        Ok(match element_vt {
+            VT_VARIANT => {
+                let items = std::slice::from_raw_parts(raw as *const VARIANT, count);
+                let values = items
+                    .iter()
+                    .map(SafeVariant::from_variant)
+                    .collect::<SageResult<Vec<_>>>()?;
+                ArrayData::Variant(values)
+            },
+            VT_BSTR => {
+                let items = std::slice::from_raw_parts(raw as *const BSTR, count);
+                ArrayData::BStr(items.iter().map(|bstr| bstr.to_string()).collect())
+            },
+            VT_I4 => ArrayData::I4(std::slice::from_raw_parts(raw as *const i32, count).to_vec()),
+            VT_R8 => ArrayData::R8(std::slice::from_raw_parts(raw as *const f64, count).to_vec()),
+            _ => ArrayData::UI1(std::slice::from_raw_parts(raw as *const u8, count).to_vec()),
+        })
+    }
+}
+
+/// Crée un SAFEARRAY COM à partir d'un `SafeArray` - l'appelant en devient propriétaire
+fn create_safe_array(array: &SafeArray) -> SageResult<(*mut SAFEARRAY, VARENUM)> {
+    let element_vt = match array.data() {
+        ArrayData::Variant(_) => VT_VARIANT,
+        ArrayData::BStr(_) => VT_BSTR,
+        ArrayData::I4(_) => VT_I4,
+        ArrayData::R8(_) => VT_R8,
+        ArrayData::UI1(_) => VT_UI1,
+    };
+    let bounds: Vec<SAFEARRAYBOUND> = array
+        .bounds()
+        .iter()
+        .map(|b| SAFEARRAYBOUND { cElements: b.len, lLbound: b.lower })
+        .collect();
+
+    unsafe {
+        let parray = SafeArrayCreate(element_vt, bounds.len() as u32, bounds.as_ptr());
+        if parray.is_null() {
+            return Err(SageError::InternalError(
+                "SafeArrayCreate a échoué".to_string(),
+            ));
+        }
+        if array.is_empty() {
+            return Ok((parray, element_vt));
+        }
+
+        let mut raw = std::ptr::null_mut();
+        if let Err(e) = SafeArrayAccessData(parray, &mut raw) {
+            let _ = SafeArrayDestroy(parray);
+            return Err(e.into());
+        }
+
+        // Les éléments VARIANT/BSTR créés sont libérés par SafeArrayDestroy en cas d'échec
+        let mut written = Ok(());
+        match array.data() {
+            ArrayData::Variant(values) => {
+                for (i, value) in values.iter().enumerate() {
+                    match value.to_variant() {
+                        Ok(variant) => std::ptr::write((raw as *mut VARIANT).add(i), variant.into_raw()),
+                        Err(e) => {
+                            written = Err(e);
+                            break;
+                        }
+                    }
+                }
+            },
+            ArrayData::BStr(values) => {
+                for (i, value) in values.iter().enumerate() {
+                    std::ptr::write((raw as *mut BSTR).add(i), BSTR::from(value.as_str()));
+                }
+            },
+            ArrayData::I4(values) => {
+                std::ptr::copy_nonoverlapping(values.as_ptr(), raw as *mut i32, values.len())
+            },
+            ArrayData::R8(values) => {
+                std::ptr::copy_nonoverlapping(values.as_ptr(), raw as *mut f64, values.len())
+            },
+            ArrayData::UI1(values) => {
+                std::ptr::copy_nonoverlapping(values.as_ptr(), raw as *mut u8, values.len())
+            },
+        }
+
+        let unaccess = SafeArrayUnaccessData(parray);
+        if let Err(e) = written {
+            let _ = SafeArrayDestroy(parray);
+            return Err(e);
+        }
+        if let Err(e) = unaccess {
+            let _ = SafeArrayDestroy(parray);
+            return Err(e.into());
+        }
+
+        Ok((parray, element_vt))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scalar_roundtrip() {
+        let values = vec![
+            SafeVariant::Null,
+            SafeVariant::I1(-5),
+            SafeVariant::UI1(200),
+            SafeVariant::UI2(60_000),
+            SafeVariant::UI4(4_000_000_000),
+            SafeVariant::I8(-9_000_000_000),
+            SafeVariant::UI8(18_000_000_000),
+            SafeVariant::Currency(1_234_500),
+            SafeVariant::Date(45123.5),
+            SafeVariant::Missing,
+            SafeVariant::Error(HResult(0x8002_0004u32 as i32)),
+        ];
+
+        for value in values {
+            let back = value.to_variant().unwrap().to_safe_variant().unwrap();
+            assert_eq!(format!("{:?}", back), format!("{:?}", value));
+        }
+    }
+
+    #[test]
+    fn test_unknown_is_rejected() {
+        let result = SafeVariant::Unknown("IUnknown object".to_string()).to_variant();
+        assert!(matches!(result, Err(SageError::ConversionError { .. })));
+    }
+}
@@ -0,0 +1,311 @@
+use super::backend::{DetachedDispatch, DispatchObject};
+use super::currency::{self, RoundingMode};
+use super::ole_date;
+use super::out_param::OutParam;
+use super::safe_array::SafeArray;
+use super::SafeVariant;
+use crate::errors::HResult;
+use chrono::NaiveDateTime;
+use rust_decimal::Decimal;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::str::FromStr;
+
+/// Format des dates sérialisées (ISO 8601 à la milliseconde)
+const DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.3f";
+
+/// Valeur d'une date : texte ISO 8601 quand il restitue exactement la date
+/// OLE, valeur brute sinon (date hors plage, fraction plus fine que la
+/// milliseconde)
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum DateRepr {
+    Text(String),
+    Raw(Float<f64>),
+}
+
+/// Flottant sérialisé : nombre JSON s'il est fini, texte sinon (`"NaN"`,
+/// `"inf"`, `"-inf"`), JSON n'ayant pas de représentation pour ces valeurs
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum Float<T> {
+    Finite(T),
+    NonFinite(String),
+}
+
+impl<T: Copy + Into<f64> + ToString + FromStr> Float<T> {
+    fn new(value: T) -> Self {
+        if value.into().is_finite() {
+            Float::Finite(value)
+        } else {
+            Float::NonFinite(value.to_string())
+        }
+    }
+
+    fn value<E: serde::de::Error>(self) -> Result<T, E> {
+        match self {
+            Float::Finite(value) => Ok(value),
+            Float::NonFinite(text) => text.parse().map_err(|_| E::custom(format!("flottant invalide: {}", text))),
+        }
+    }
+}
+
+/// Représentation d'un objet d'automation : son handle opaque
+#[derive(Serialize, Deserialize)]
+struct DispatchHandle {
+    handle: u64,
+}
+
+/// Forme sérialisée d'un SafeVariant (écriture, sans copie des données)
+///
+/// Le type est toujours explicite : `{"type":"Currency","value":"12.3400"}`.
+/// Montants et dates sont écrits en texte pour rester exacts ; une date que
+/// le texte ne restitue pas exactement garde sa valeur OLE brute.
+#[derive(Serialize)]
+#[serde(tag = "type", content = "value")]
+enum VariantRef<'a> {
+    Empty,
+    Null,
+    Bool(bool),
+    I2(i16),
+    I4(i32),
+    R4(Float<f32>),
+    R8(Float<f64>),
+    Currency(String),
+    Decimal(String),
+    Date(DateRepr),
+    BStr(&'a str),
+    Error(HResult),
+    I1(i8),
+    UI1(u8),
+    UI2(u16),
+    UI4(u32),
+    I8(i64),
+    UI8(u64),
+    Dispatch(DispatchHandle),
+    Unknown(&'a str),
+    Array(&'a SafeArray),
+    ByRef(SafeVariant),
+    Missing,
+}
+
+/// Forme sérialisée d'un SafeVariant (lecture)
+#[derive(Deserialize)]
+#[serde(tag = "type", content = "value")]
+enum VariantOwned {
+    Empty,
+    Null,
+    Bool(bool),
+    I2(i16),
+    I4(i32),
+    R4(Float<f32>),
+    R8(Float<f64>),
+    Currency(String),
+    Decimal(String),
+    Date(DateRepr),
+    BStr(String),
+    Error(HResult),
+    I1(i8),
+    UI1(u8),
+    UI2(u16),
+    UI4(u32),
+    I8(i64),
+    UI8(u64),
+    Dispatch(DispatchHandle),
+    Unknown(String),
+    Array(SafeArray),
+    ByRef(Box<SafeVariant>),
+    Missing,
+}
+
+impl Serialize for SafeVariant {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let repr = match self {
+            SafeVariant::Empty => VariantRef::Empty,
+            SafeVariant::Null => VariantRef::Null,
+            SafeVariant::Bool(b) => VariantRef::Bool(*b),
+            SafeVariant::I2(i) => VariantRef::I2(*i),
+            SafeVariant::I4(i) => VariantRef::I4(*i),
+            SafeVariant::R4(f) => VariantRef::R4(Float::new(*f)),
+            SafeVariant::R8(f) => VariantRef::R8(Float::new(*f)),
+            SafeVariant::Currency(c) => VariantRef::Currency(currency::currency_to_decimal(*c).to_string()),
+            SafeVariant::Decimal(d) => VariantRef::Decimal(d.to_string()),
+            SafeVariant::Date(d) => VariantRef::Date(date_repr(*d)),
+            SafeVariant::BStr(s) => VariantRef::BStr(s),
+            SafeVariant::Error(hr) => VariantRef::Error(*hr),
+            SafeVariant::I1(i) => VariantRef::I1(*i),
+            SafeVariant::UI1(i) => VariantRef::UI1(*i),
+            SafeVariant::UI2(i) => VariantRef::UI2(*i),
+            SafeVariant::UI4(i) => VariantRef::UI4(*i),
+            SafeVariant::I8(i) => VariantRef::I8(*i),
+            SafeVariant::UI8(i) => VariantRef::UI8(*i),
+            SafeVariant::Dispatch(object) => VariantRef::Dispatch(DispatchHandle { handle: object.handle() }),
+            SafeVariant::Unknown(desc) => VariantRef::Unknown(desc),
+            SafeVariant::Array(array) => VariantRef::Array(array),
+            // Seule la valeur courante du paramètre [in, out] est écrite
+            SafeVariant::ByRef(slot) => VariantRef::ByRef(slot.value()),
+            SafeVariant::Missing => VariantRef::Missing,
+        };
+        repr.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SafeVariant {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = match VariantOwned::deserialize(deserializer)? {
+            VariantOwned::Empty => SafeVariant::Empty,
+            VariantOwned::Null => SafeVariant::Null,
+            VariantOwned::Bool(b) => SafeVariant::Bool(b),
+            VariantOwned::I2(i) => SafeVariant::I2(i),
+            VariantOwned::I4(i) => SafeVariant::I4(i),
+            VariantOwned::R4(f) => SafeVariant::R4(f.value()?),
+            VariantOwned::R8(f) => SafeVariant::R8(f.value()?),
+            VariantOwned::Currency(text) => {
+                let amount = parse_decimal(&text)?;
+                // Un montant CY a au plus 4 décimales : aucun arrondi à ce stade
+                SafeVariant::Currency(
+                    currency::decimal_to_currency(amount, RoundingMode::Bankers).map_err(D::Error::custom)?,
+                )
+            }
+            VariantOwned::Decimal(text) => SafeVariant::Decimal(parse_decimal(&text)?),
+            VariantOwned::Date(DateRepr::Raw(raw)) => SafeVariant::Date(raw.value()?),
+            VariantOwned::Date(DateRepr::Text(text)) => {
+                match NaiveDateTime::parse_from_str(&text, DATE_FORMAT)
+                    .or_else(|_| NaiveDateTime::parse_from_str(&text, "%Y-%m-%dT%H:%M:%S"))
+                {
+                    Ok(datetime) => {
+                        SafeVariant::Date(ole_date::naive_datetime_to_ole_date(datetime).map_err(D::Error::custom)?)
+                    }
+                    // Valeur brute non finie, écrite en texte
+                    Err(_) => match text.parse::<f64>() {
+                        Ok(raw) if !raw.is_finite() => SafeVariant::Date(raw),
+                        _ => return Err(D::Error::custom(format!("date invalide: {}", text))),
+                    },
+                }
+            }
+            VariantOwned::BStr(s) => SafeVariant::BStr(s),
+            VariantOwned::Error(hr) => SafeVariant::Error(hr),
+            VariantOwned::I1(i) => SafeVariant::I1(i),
+            VariantOwned::UI1(i) => SafeVariant::UI1(i),
+            VariantOwned::UI2(i) => SafeVariant::UI2(i),
+            VariantOwned::UI4(i) => SafeVariant::UI4(i),
+            VariantOwned::I8(i) => SafeVariant::I8(i),
+            VariantOwned::UI8(i) => SafeVariant::UI8(i),
+            // L'objet d'origine n'existe plus : seul son handle est restitué
+            VariantOwned::Dispatch(DispatchHandle { handle }) => {
+                SafeVariant::Dispatch(DispatchObject::new(DetachedDispatch::new(handle)))
+            }
+            VariantOwned::Unknown(desc) => SafeVariant::Unknown(desc),
+            VariantOwned::Array(array) => SafeVariant::Array(array),
+            VariantOwned::ByRef(value) => SafeVariant::ByRef(OutParam::new(*value)),
+            VariantOwned::Missing => SafeVariant::Missing,
+        };
+        Ok(value)
+    }
+}
+
+fn date_repr(value: f64) -> DateRepr {
+    match ole_date::ole_date_to_naive_datetime(value) {
+        Ok(datetime) if ole_date::naive_datetime_to_ole_date(datetime).ok() == Some(value) => {
+            DateRepr::Text(datetime.format(DATE_FORMAT).to_string())
+        }
+        _ => DateRepr::Raw(Float::new(value)),
+    }
+}
+
+fn parse_decimal<E: serde::de::Error>(text: &str) -> Result<Decimal, E> {
+    Decimal::from_str(text).map_err(|_| E::custom(format!("montant invalide: {}", text)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::com::{ArrayBound, ArrayData, FakeObject};
+    use chrono::NaiveDate;
+    use serde_json::json;
+
+    fn roundtrip(value: &SafeVariant) -> SafeVariant {
+        serde_json::from_str(&serde_json::to_string(value).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_tagged_representation() {
+        let amount = SafeVariant::currency_from_decimal(Decimal::new(123400, 4), RoundingMode::Bankers).unwrap();
+        assert_eq!(serde_json::to_value(&amount).unwrap(), json!({"type": "Currency", "value": "12.3400"}));
+        assert_eq!(serde_json::to_value(SafeVariant::Missing).unwrap(), json!({"type": "Missing"}));
+        assert_eq!(
+            serde_json::to_value(SafeVariant::Error(HResult::DISP_E_PARAMNOTFOUND)).unwrap(),
+            json!({"type": "Error", "value": "0x80020004"})
+        );
+
+        let date = NaiveDate::from_ymd_opt(2024, 3, 31).unwrap().and_hms_milli_opt(14, 30, 0, 250).unwrap();
+        let value = SafeVariant::from_naive_datetime(date).unwrap();
+        assert_eq!(serde_json::to_value(&value).unwrap(), json!({"type": "Date", "value": "2024-03-31T14:30:00.250"}));
+        assert_eq!(roundtrip(&value).to_naive_datetime().unwrap(), date);
+    }
+
+    #[test]
+    fn test_roundtrip_preserves_types() {
+        let amount = roundtrip(&SafeVariant::Currency(-1_234_567));
+        assert!(matches!(amount, SafeVariant::Currency(-1_234_567)));
+        assert!(matches!(roundtrip(&SafeVariant::UI8(u64::MAX)), SafeVariant::UI8(u64::MAX)));
+
+        let array = SafeArray::new(
+            vec![ArrayBound::new(1, 2), ArrayBound::new(0, 1)],
+            ArrayData::Variant(vec![SafeVariant::from("VTE"), SafeVariant::Decimal(Decimal::new(5, 1))]),
+        )
+        .unwrap();
+        let restored = roundtrip(&SafeVariant::Array(array));
+        let array = restored.to_array().unwrap();
+        assert_eq!(array.bounds()[0], ArrayBound::new(1, 2));
+        assert!(matches!(array.get(&[2, 0]), Some(SafeVariant::Decimal(d)) if d == Decimal::new(5, 1)));
+
+        // Bornes incohérentes rejetées à la lecture
+        let invalid = json!({"type": "Array", "value": {"bounds": [{"lower": 0, "len": 3}], "data": {"type": "I4", "values": [1]}}});
+        assert!(serde_json::from_value::<SafeVariant>(invalid).is_err());
+    }
+
+    #[test]
+    fn test_non_finite_floats_roundtrip() {
+        assert_eq!(serde_json::to_value(SafeVariant::R8(f64::NAN)).unwrap(), json!({"type": "R8", "value": "NaN"}));
+        assert_eq!(serde_json::to_value(SafeVariant::R8(1.5)).unwrap(), json!({"type": "R8", "value": 1.5}));
+
+        assert!(matches!(roundtrip(&SafeVariant::R8(f64::NAN)), SafeVariant::R8(f) if f.is_nan()));
+        assert!(matches!(roundtrip(&SafeVariant::R8(f64::INFINITY)), SafeVariant::R8(f64::INFINITY)));
+        assert!(matches!(roundtrip(&SafeVariant::R8(f64::NEG_INFINITY)), SafeVariant::R8(f64::NEG_INFINITY)));
+        assert!(matches!(roundtrip(&SafeVariant::R4(f32::NEG_INFINITY)), SafeVariant::R4(f32::NEG_INFINITY)));
+        assert!(serde_json::from_value::<SafeVariant>(json!({"type": "R8", "value": "abc"})).is_err());
+    }
+
+    #[test]
+    fn test_inexact_or_out_of_range_dates_keep_raw_value() {
+        // Hors de la plage VT_DATE : la sérialisation ne doit pas échouer
+        let far = SafeVariant::Date(3_000_000.5);
+        assert_eq!(serde_json::to_value(&far).unwrap(), json!({"type": "Date", "value": 3_000_000.5}));
+        assert!(matches!(roundtrip(&far), SafeVariant::Date(d) if d == 3_000_000.5));
+
+        // Fraction plus fine que la milliseconde : valeur restituée à l'identique
+        let precise = 45123.0 + 1e-9;
+        assert!(matches!(roundtrip(&SafeVariant::Date(precise)), SafeVariant::Date(d) if d == precise));
+        assert!(matches!(roundtrip(&SafeVariant::Date(f64::NAN)), SafeVariant::Date(d) if d.is_nan()));
+    }
+
+    #[test]
+    fn test_dispatch_as_opaque_handle() {
+        let object = SafeVariant::from(FakeObject::new("IBOJournal3"));
+        let handle = object.to_object().unwrap().handle();
+
+        let json = serde_json::to_value(&object).unwrap();
+        assert_eq!(json, json!({"type": "Dispatch", "value": {"handle": handle}}));
+
+        let restored: SafeVariant = serde_json::from_value(json).unwrap();
+        let restored = restored.to_object().unwrap();
+        assert_eq!(restored.downcast_ref::<DetachedDispatch>().map(|d| d.handle()), Some(handle));
+        assert_eq!(restored.handle(), handle);
+
+        // Un objet créé après la libération du premier reçoit un autre handle
+        drop(object);
+        let next = DispatchObject::new(FakeObject::new("IBOJournal3"));
+        assert!(next.handle() > handle);
+    }
+}
@@ -0,0 +1,156 @@
+use super::{HResult, Language};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// DISPID de l'argument nommé portant la valeur d'un PROPPUT
+const DISPID_PROPERTYPUT: i32 = -3;
+
+/// Détail d'un échec de `IDispatch::Invoke`, issu de l'EXCEPINFO
+///
+/// Conserve tout ce que le serveur d'automation a renvoyé, en particulier le
+/// numéro d'erreur Sage dont le support a besoin pour diagnostiquer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutomationError {
+    /// Membre invoqué
+    pub member: String,
+    /// DISPID du membre
+    pub dispid: i32,
+    /// HRESULT retourné par Invoke (DISP_E_EXCEPTION, DISP_E_TYPEMISMATCH...)
+    pub hresult: HResult,
+    /// SCODE de l'EXCEPINFO
+    pub scode: HResult,
+    /// Code d'erreur applicatif (`wCode`), 0 si le serveur utilise le SCODE
+    pub code: u16,
+    /// Source de l'exception (ex: "Objets100c.Cpta.Stream.1")
+    pub source: String,
+    /// Description de l'erreur
+    pub description: String,
+    /// Fichier d'aide associé
+    pub help_file: Option<String>,
+    /// Contexte dans le fichier d'aide
+    pub help_context: u32,
+    /// Position du paramètre fautif dans la signature (à partir de 0)
+    pub arg_position: Option<usize>,
+}
+
+impl AutomationError {
+    /// Numéro d'erreur tel que le verrait VB (`Err.Number`) : le `wCode` s'il
+    /// est renseigné, sinon le SCODE de l'exception, sinon le HRESULT d'Invoke
+    pub fn error_number(&self) -> i32 {
+        if self.code != 0 {
+            self.code as i32
+        } else if self.scode.0 != 0 {
+            self.scode.0
+        } else {
+            self.hresult.0
+        }
+    }
+
+    /// Message de l'erreur dans la langue demandée
+    pub fn message(&self, language: Language) -> String {
+        let mut message = language.pick(
+            format!(
+                "Erreur lors de l'appel de '{}' (ID: {}, {}): {}",
+                self.member, self.dispid, self.hresult, self.description
+            ),
+            format!(
+                "Call to '{}' (ID: {}, {}) failed: {}",
+                self.member, self.dispid, self.hresult, self.description
+            ),
+        );
+        if !self.source.is_empty() {
+            message.push_str(&format!(" [source: {}]", self.source));
+        }
+        if self.error_number() != self.hresult.0 {
+            message.push_str(&language.pick(
+                format!(" [erreur Sage: {}]", self.error_number()),
+                format!(" [Sage error: {}]", self.error_number()),
+            ));
+        }
+        if let Some(position) = self.arg_position {
+            message.push_str(&language.pick(
+                format!(" [paramètre n°{}]", position + 1),
+                format!(" [parameter #{}]", position + 1),
+            ));
+        }
+        message
+    }
+
+    /// Convertit l'index `puArgErr` d'Invoke en position dans la signature
+    ///
+    /// `rgvarg` contient d'abord les arguments nommés (dans l'ordre de
+    /// `named_ids`, la valeur d'un PROPPUT portant DISPID_PROPERTYPUT), puis
+    /// les `positional` arguments positionnels de droite à gauche. Seuls
+    /// DISP_E_TYPEMISMATCH et DISP_E_PARAMNOTFOUND renseignent cet index.
+    pub fn arg_position(
+        hresult: HResult,
+        arg_err: u32,
+        positional: usize,
+        named_ids: &[i32],
+    ) -> Option<usize> {
+        if hresult != HResult::DISP_E_TYPEMISMATCH && hresult != HResult::DISP_E_PARAMNOTFOUND {
+            return None;
+        }
+
+        let index = arg_err as usize;
+        match named_ids.get(index) {
+            // La valeur affectée suit les index de la propriété
+            Some(&DISPID_PROPERTYPUT) => Some(positional),
+            Some(&param_id) => usize::try_from(param_id).ok(),
+            None => positional.checked_sub(index - named_ids.len() + 1),
+        }
+    }
+}
+
+/// Message dans la langue courante (`Language::current()`)
+impl fmt::Display for AutomationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message(Language::current()))
+    }
+}
+
+impl std::error::Error for AutomationError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arg_position_from_rgvarg_index() {
+        let mismatch = HResult::DISP_E_TYPEMISMATCH;
+
+        // Solde(a, b, c) : rgvarg = [c, b, a]
+        assert_eq!(AutomationError::arg_position(mismatch, 0, 3, &[]), Some(2));
+        assert_eq!(AutomationError::arg_position(mismatch, 2, 3, &[]), Some(0));
+        // Nommé en tête de rgvarg, puis positionnels inversés
+        assert_eq!(AutomationError::arg_position(mismatch, 0, 1, &[2]), Some(2));
+        assert_eq!(AutomationError::arg_position(mismatch, 1, 1, &[2]), Some(0));
+        // Valeur d'un PROPPUT indexé : après l'index
+        assert_eq!(AutomationError::arg_position(mismatch, 0, 1, &[DISPID_PROPERTYPUT]), Some(1));
+        // Index hors limites ou HRESULT sans puArgErr
+        assert_eq!(AutomationError::arg_position(mismatch, 5, 3, &[]), None);
+        assert_eq!(AutomationError::arg_position(HResult::DISP_E_EXCEPTION, 0, 3, &[]), None);
+    }
+
+    #[test]
+    fn test_error_number_and_display() {
+        let error = AutomationError {
+            member: "ReadNumero".to_string(),
+            dispid: 2,
+            hresult: HResult::DISP_E_EXCEPTION,
+            scode: HResult(0x80040BB9u32 as i32),
+            code: 0,
+            source: "Objets100c".to_string(),
+            description: "Enregistrement non trouvé".to_string(),
+            help_file: None,
+            help_context: 0,
+            arg_position: None,
+        };
+
+        assert_eq!(error.error_number(), 0x80040BB9u32 as i32);
+        let message = error.to_string();
+        assert!(message.contains("0x80020009"));
+        assert!(message.contains("[source: Objets100c]"));
+        assert!(message.contains("Enregistrement non trouvé"));
+    }
+}
@@ -0,0 +1,94 @@
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// Nature métier d'une erreur Objets Métier, indépendante de son code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum SageErrorKind {
+    /// Enregistrement verrouillé par un autre utilisateur
+    RecordLocked,
+    /// Clé en double (numéro de pièce, code tiers... déjà utilisé)
+    DuplicateKey,
+    /// Enregistrement introuvable
+    RecordNotFound,
+    /// Base de données non ouverte
+    DatabaseNotOpen,
+    /// Licence absente ou invalide
+    LicenseInvalid,
+    /// Écriture sur une période clôturée
+    ClosedPeriod,
+}
+
+/// Codes connus
+///
+/// Les numéros Objets Métier sont ceux de l'EXCEPINFO (`wCode`, `Err.Number`
+/// côté VB) ; les HRESULT OLE DB / Win32 couvrent les échecs remontés sans
+/// numéro Sage. `register_error_code` complète ou corrige cette table pour
+/// une version donnée.
+const KNOWN_CODES: &[(u32, SageErrorKind)] = &[
+    // Numéros d'erreur Objets Métier
+    (12_011, SageErrorKind::DatabaseNotOpen), // Base non ouverte
+    (12_022, SageErrorKind::RecordNotFound),  // Enregistrement inexistant
+    (12_023, SageErrorKind::DuplicateKey),    // Enregistrement déjà existant
+    (12_024, SageErrorKind::RecordLocked),    // Enregistrement en cours d'utilisation
+    (12_061, SageErrorKind::ClosedPeriod),    // Date hors exercice ou période clôturée
+    (12_101, SageErrorKind::LicenseInvalid),  // Licence non valide pour ce module
+    // HRESULT OLE DB / Win32
+    (0x80040E38, SageErrorKind::RecordLocked),   // DB_E_CONCURRENCYVIOLATION
+    (0x80070020, SageErrorKind::RecordLocked),   // ERROR_SHARING_VIOLATION
+    (0x80070021, SageErrorKind::RecordLocked),   // ERROR_LOCK_VIOLATION
+    (0x80040E2F, SageErrorKind::DuplicateKey),   // DB_E_INTEGRITYVIOLATION
+    (0x80040E19, SageErrorKind::RecordNotFound), // DB_E_NOTFOUND
+    (0x80040112, SageErrorKind::LicenseInvalid), // CLASS_E_NOTLICENSED
+];
+
+lazy_static! {
+    /// Codes ajoutés à l'exécution (numéros propres à une version de Sage)
+    static ref REGISTERED_CODES: RwLock<HashMap<i32, SageErrorKind>> = RwLock::new(HashMap::new());
+}
+
+/// Associe un numéro d'erreur (HRESULT ou `wCode` de l'EXCEPINFO) à une nature
+///
+/// Permet de compléter le catalogue avec les numéros relevés sur une
+/// installation donnée ; un code enregistré prime sur le catalogue intégré.
+pub fn register_error_code(code: i32, kind: SageErrorKind) {
+    REGISTERED_CODES
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(code, kind);
+}
+
+/// Nature de l'erreur correspondant au code, si elle est cataloguée
+pub fn lookup_error_code(code: i32) -> Option<SageErrorKind> {
+    let registered = REGISTERED_CODES
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(&code)
+        .copied();
+
+    registered.or_else(|| {
+        KNOWN_CODES
+            .iter()
+            .find(|(known, _)| *known as i32 == code)
+            .map(|(_, kind)| *kind)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_known_and_registered_codes() {
+        assert_eq!(lookup_error_code(0x80040E2Fu32 as i32), Some(SageErrorKind::DuplicateKey));
+        assert_eq!(lookup_error_code(0x80070021u32 as i32), Some(SageErrorKind::RecordLocked));
+        assert_eq!(lookup_error_code(0x80004005u32 as i32), None);
+
+        assert_eq!(lookup_error_code(12_024), Some(SageErrorKind::RecordLocked));
+
+        assert_eq!(lookup_error_code(13_500), None);
+        register_error_code(13_500, SageErrorKind::ClosedPeriod);
+        assert_eq!(lookup_error_code(13_500), Some(SageErrorKind::ClosedPeriod));
+    }
+}
@@ -0,0 +1,91 @@
+use super::SageError;
+use std::error::Error;
+use std::fmt;
+
+/// Erreur d'origine conservée sous une `SageError` (ex: `windows::core::Error`)
+pub struct ErrorSource(Box<dyn Error + Send + Sync + 'static>);
+
+impl ErrorSource {
+    pub fn new<E: Error + Send + Sync + 'static>(error: E) -> Self {
+        ErrorSource(Box::new(error))
+    }
+
+    /// Erreur d'origine, à retrouver par `downcast_ref`
+    pub fn as_error(&self) -> &(dyn Error + 'static) {
+        self.0.as_ref()
+    }
+}
+
+impl fmt::Debug for ErrorSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+/// Ajout de contexte aux résultats, par exemple
+/// `app.open().context("ouverture de BIJOU.MAE")?`
+///
+/// Chaque appel empile un `SageError::Context` ; l'erreur d'origine reste
+/// accessible par `root()` et la chaîne `source()`.
+pub trait ResultExt<T> {
+    /// Ajoute un contexte fixe
+    fn context<C: Into<String>>(self, context: C) -> Result<T, SageError>;
+
+    /// Ajoute un contexte calculé uniquement en cas d'erreur
+    fn with_context<C: Into<String>, F: FnOnce() -> C>(self, context: F) -> Result<T, SageError>;
+}
+
+impl<T, E: Into<SageError>> ResultExt<T> for Result<T, E> {
+    fn context<C: Into<String>>(self, context: C) -> Result<T, SageError> {
+        self.map_err(|error| error.into().context(context))
+    }
+
+    fn with_context<C: Into<String>, F: FnOnce() -> C>(self, context: F) -> Result<T, SageError> {
+        self.map_err(|error| error.into().context(context()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::{HResult, SageResult};
+
+    #[derive(Debug)]
+    struct OriginalError;
+
+    impl fmt::Display for OriginalError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "erreur d'origine")
+        }
+    }
+
+    impl Error for OriginalError {}
+
+    #[test]
+    fn test_context_frames_and_source_chain() {
+        let failing: SageResult<()> = Err(SageError::from_code_with_source(
+            HResult(0x80040E38u32 as i32),
+            "verrou",
+            Some(ErrorSource::new(OriginalError)),
+        ));
+        let error = failing
+            .context("Loggable.UserName")
+            .with_context(|| format!("ouverture de {}", "BIJOU.MAE"))
+            .unwrap_err();
+
+        assert_eq!(error.contexts(), vec!["ouverture de BIJOU.MAE", "Loggable.UserName"]);
+        assert!(error.to_string().starts_with("ouverture de BIJOU.MAE: Loggable.UserName: "));
+        assert!(error.is_lock_conflict());
+        assert_eq!(error.code(), "SAGE-DATA-001");
+
+        // ouverture -> Loggable -> verrou -> erreur d'origine
+        let mut chain = Vec::new();
+        let mut current: Option<&(dyn Error + 'static)> = Some(&error);
+        while let Some(e) = current {
+            chain.push(e);
+            current = e.source();
+        }
+        assert_eq!(chain.len(), 4);
+        assert!(chain[3].downcast_ref::<OriginalError>().is_some());
+    }
+}
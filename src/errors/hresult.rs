@@ -0,0 +1,86 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Code HRESULT indépendant de la plateforme
+///
+/// Permet de conserver les codes d'erreur COM dans `SageError` et `SafeVariant`
+/// sans dépendre du crate `windows` sur les cibles non Windows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct HResult(pub i32);
+
+impl HResult {
+    /// Nom de membre inconnu (GetIDsOfNames)
+    pub const DISP_E_UNKNOWNNAME: HResult = HResult(0x80020006u32 as i32);
+    /// Paramètre obligatoire absent
+    pub const DISP_E_PARAMNOTFOUND: HResult = HResult(0x80020004u32 as i32);
+    /// Type de paramètre incompatible
+    pub const DISP_E_TYPEMISMATCH: HResult = HResult(0x80020005u32 as i32);
+    /// Exception levée par le serveur, détaillée dans l'EXCEPINFO
+    pub const DISP_E_EXCEPTION: HResult = HResult(0x80020009u32 as i32);
+    /// Appel refusé par le serveur COM (occupé)
+    pub const RPC_E_CALL_REJECTED: HResult = HResult(0x80010001u32 as i32);
+    /// Le serveur COM demande de réessayer plus tard
+    pub const RPC_E_SERVERCALL_RETRYLATER: HResult = HResult(0x8001010Au32 as i32);
+    /// Le thread est déjà initialisé avec un autre modèle d'apartment
+    pub const RPC_E_CHANGED_MODE: HResult = HResult(0x80010106u32 as i32);
+    /// COM n'est pas initialisé sur le thread appelant
+    pub const CO_E_NOTINITIALIZED: HResult = HResult(0x800401F0u32 as i32);
+
+    /// Vérifie si le code indique un succès (bit de sévérité à 0)
+    pub fn is_ok(self) -> bool {
+        self.0 >= 0
+    }
+
+    /// Vérifie si le code indique un échec
+    pub fn is_err(self) -> bool {
+        !self.is_ok()
+    }
+}
+
+impl fmt::Display for HResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{:08X}", self.0)
+    }
+}
+
+/// Sérialisé en hexadécimal ("0x80040154"), comme dans la documentation Sage
+impl Serialize for HResult {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for HResult {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        let hex = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")).unwrap_or(&text);
+        u32::from_str_radix(hex, 16)
+            .map(|code| HResult(code as i32))
+            .map_err(|_| serde::de::Error::custom(format!("HRESULT invalide: {}", text)))
+    }
+}
+
+impl From<i32> for HResult {
+    fn from(code: i32) -> Self {
+        HResult(code)
+    }
+}
+
+#[cfg(all(windows, feature = "com"))]
+impl From<windows::core::HRESULT> for HResult {
+    fn from(hresult: windows::core::HRESULT) -> Self {
+        HResult(hresult.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hresult_severity() {
+        assert!(HResult(0).is_ok());
+        assert!(HResult(0x80040154u32 as i32).is_err());
+        assert_eq!(HResult(0x80070005u32 as i32).to_string(), "0x80070005");
+    }
+}
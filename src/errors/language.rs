@@ -0,0 +1,39 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Langue de rendu des messages d'erreur
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    #[default]
+    French,
+    English,
+}
+
+/// Langue courante, partagée par tout le processus (français par défaut)
+static CURRENT_LANGUAGE: AtomicU8 = AtomicU8::new(0);
+
+impl Language {
+    /// Langue utilisée par `Display` pour `SageError`
+    pub fn current() -> Self {
+        match CURRENT_LANGUAGE.load(Ordering::Relaxed) {
+            1 => Language::English,
+            _ => Language::French,
+        }
+    }
+
+    /// Change la langue des messages d'erreur pour tout le processus
+    pub fn set_current(language: Language) {
+        let value = match language {
+            Language::French => 0,
+            Language::English => 1,
+        };
+        CURRENT_LANGUAGE.store(value, Ordering::Relaxed);
+    }
+
+    /// Choisit le texte correspondant à la langue
+    pub(crate) fn pick(self, french: String, english: String) -> String {
+        match self {
+            Language::French => french,
+            Language::English => english,
+        }
+    }
+}
@@ -1,4 +1,14 @@
+pub mod automation;
+pub mod catalog;
+pub mod context;
+pub mod hresult;
+pub mod language;
 pub mod sage_error;
 
-pub use sage_error::SageError;
+pub use automation::AutomationError;
+pub use catalog::{SageErrorKind, lookup_error_code, register_error_code};
+pub use context::{ErrorSource, ResultExt};
+pub use hresult::HResult;
+pub use language::Language;
+pub use sage_error::{ErrorReport, SageError};
 pub type SageResult<T> = Result<T, SageError>;
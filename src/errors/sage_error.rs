@@ -1,12 +1,87 @@
+use super::{AutomationError, ErrorSource, HResult, Language, SageErrorKind, lookup_error_code};
+use serde::{Deserialize, Serialize};
 use std::fmt;
-use windows::core::{Error as WindowsError, HRESULT};
+#[cfg(all(windows, feature = "com"))]
+use windows::core::Error as WindowsError;
 
 /// Erreurs spécifiques à l'API Sage 100c
-#[derive(Debug)]
+///
+/// La sérialisation donne le nom de la variante et ses champs, par exemple
+/// `{"type":"RecordNotFound","details":{"hresult":"0x80040E19","message":"..."}}`.
+/// Relire cette forme restitue la variante et ses champs, sans l'erreur
+/// d'origine (`source`).
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", content = "details")]
 #[allow(dead_code)] // Les variantes seront utilisées dans les futures versions
 pub enum SageError {
     /// Erreur COM générique
-    ComError { hresult: HRESULT, message: String },
+    ComError {
+        hresult: HResult,
+        message: String,
+        /// Erreur d'origine (ex: `windows::core::Error`), exposée par `source()`
+        #[serde(skip)]
+        source: Option<ErrorSource>,
+    },
+
+    /// Enregistrement verrouillé par un autre utilisateur
+    RecordLocked {
+        hresult: HResult,
+        message: String,
+        /// Erreur d'origine (ex: `windows::core::Error`), exposée par `source()`
+        #[serde(skip)]
+        source: Option<ErrorSource>,
+        /// Exception d'automation classée, avec le numéro d'erreur Sage
+        #[serde(skip_serializing_if = "Option::is_none")]
+        automation: Option<Box<AutomationError>>,
+    },
+
+    /// Clé en double lors d'une création
+    DuplicateKey {
+        hresult: HResult,
+        message: String,
+        /// Erreur d'origine (ex: `windows::core::Error`), exposée par `source()`
+        #[serde(skip)]
+        source: Option<ErrorSource>,
+        /// Exception d'automation classée, avec le numéro d'erreur Sage
+        #[serde(skip_serializing_if = "Option::is_none")]
+        automation: Option<Box<AutomationError>>,
+    },
+
+    /// Enregistrement introuvable
+    RecordNotFound {
+        hresult: HResult,
+        message: String,
+        /// Erreur d'origine (ex: `windows::core::Error`), exposée par `source()`
+        #[serde(skip)]
+        source: Option<ErrorSource>,
+        /// Exception d'automation classée, avec le numéro d'erreur Sage
+        #[serde(skip_serializing_if = "Option::is_none")]
+        automation: Option<Box<AutomationError>>,
+    },
+
+    /// Licence Sage absente ou invalide
+    LicenseInvalid {
+        hresult: HResult,
+        message: String,
+        /// Erreur d'origine (ex: `windows::core::Error`), exposée par `source()`
+        #[serde(skip)]
+        source: Option<ErrorSource>,
+        /// Exception d'automation classée, avec le numéro d'erreur Sage
+        #[serde(skip_serializing_if = "Option::is_none")]
+        automation: Option<Box<AutomationError>>,
+    },
+
+    /// Écriture sur une période clôturée
+    ClosedPeriod {
+        hresult: HResult,
+        message: String,
+        /// Erreur d'origine (ex: `windows::core::Error`), exposée par `source()`
+        #[serde(skip)]
+        source: Option<ErrorSource>,
+        /// Exception d'automation classée, avec le numéro d'erreur Sage
+        #[serde(skip_serializing_if = "Option::is_none")]
+        automation: Option<Box<AutomationError>>,
+    },
 
     /// Erreur de connexion à la base de données
     ConnectionError {
@@ -24,6 +99,9 @@ pub enum SageError {
         message: String,
     },
 
+    /// Exception levée par le serveur d'automation lors d'un Invoke
+    Automation(Box<AutomationError>),
+
     /// Erreur de conversion de données
     ConversionError {
         from_type: String,
@@ -39,10 +117,23 @@ pub enum SageError {
     },
 
     /// Base de données non ouverte
-    DatabaseNotOpen,
+    DatabaseNotOpen {
+        /// Exception d'automation classée, avec le numéro d'erreur Sage
+        #[serde(skip_serializing_if = "Option::is_none")]
+        automation: Option<Box<AutomationError>>,
+        /// Erreur d'origine (ex: `windows::core::Error`), exposée par `source()`
+        #[serde(skip)]
+        source: Option<ErrorSource>,
+    },
 
     /// CLSID non trouvé ou DLL non enregistrée
-    ClassNotRegistered(String),
+    ClassNotRegistered {
+        /// CLSID, ProgID ou module recherché
+        class: String,
+        /// Erreur d'origine (ex: `windows::core::Error`), exposée par `source()`
+        #[serde(skip)]
+        source: Option<ErrorSource>,
+    },
 
     /// Erreur de format de paramètre
     InvalidParameter {
@@ -56,104 +147,235 @@ pub enum SageError {
 
     /// Erreur interne inattendue
     InternalError(String),
+
+    /// Erreur enrichie d'un contexte d'appel (voir `ResultExt::context`)
+    Context {
+        context: String,
+        source: Box<SageError>,
+    },
 }
 
-impl fmt::Display for SageError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl SageError {
+    /// Code stable de l'erreur, destiné aux intégrations (ex: "SAGE-CONN-001")
+    ///
+    /// Contrairement au message, ce code ne dépend ni de la langue ni du
+    /// texte renvoyé par Sage et ne change pas d'une version à l'autre.
+    pub fn code(&self) -> &'static str {
         match self {
-            SageError::ComError { hresult, message } => {
-                write!(f, "Erreur COM (0x{:08X}): {}", hresult.0, message)
-            }
+            SageError::ComError { .. } => "SAGE-COM-001",
+            SageError::ClassNotRegistered { .. } => "SAGE-COM-002",
+            SageError::Automation(_) => "SAGE-COM-003",
+            SageError::ConnectionError { .. } => "SAGE-CONN-001",
+            SageError::DatabaseNotOpen { .. } => "SAGE-CONN-002",
+            SageError::AuthenticationError { .. } => "SAGE-AUTH-001",
+            SageError::LicenseInvalid { .. } => "SAGE-AUTH-002",
+            SageError::MethodCallError { .. } => "SAGE-CALL-001",
+            SageError::InvalidParameter { .. } => "SAGE-CALL-002",
+            SageError::UnsupportedOperation(_) => "SAGE-CALL-003",
+            SageError::ConversionError { .. } => "SAGE-CONV-001",
+            SageError::ValidationError { .. } => "SAGE-VAL-001",
+            SageError::RecordLocked { .. } => "SAGE-DATA-001",
+            SageError::DuplicateKey { .. } => "SAGE-DATA-002",
+            SageError::RecordNotFound { .. } => "SAGE-DATA-003",
+            SageError::ClosedPeriod { .. } => "SAGE-DATA-004",
+            SageError::InternalError(_) => "SAGE-INT-001",
+            SageError::Context { source, .. } => source.code(),
+        }
+    }
+
+    /// Message de l'erreur dans la langue demandée
+    pub fn message(&self, language: Language) -> String {
+        let (french, english) = match self {
+            SageError::ComError { hresult, message, .. } => (
+                format!("Erreur COM ({}): {}", hresult, message),
+                format!("COM error ({}): {}", hresult, message),
+            ),
+            SageError::RecordLocked { hresult, message, .. } => (
+                format!("Enregistrement verrouillé par un autre utilisateur ({}): {}", hresult, message),
+                format!("Record locked by another user ({}): {}", hresult, message),
+            ),
+            SageError::DuplicateKey { hresult, message, .. } => (
+                format!("Clé en double ({}): {}", hresult, message),
+                format!("Duplicate key ({}): {}", hresult, message),
+            ),
+            SageError::RecordNotFound { hresult, message, .. } => (
+                format!("Enregistrement introuvable ({}): {}", hresult, message),
+                format!("Record not found ({}): {}", hresult, message),
+            ),
+            SageError::LicenseInvalid { hresult, message, .. } => (
+                format!("Licence Sage invalide ({}): {}", hresult, message),
+                format!("Invalid Sage licence ({}): {}", hresult, message),
+            ),
+            SageError::ClosedPeriod { hresult, message, .. } => (
+                format!("Période clôturée ({}): {}", hresult, message),
+                format!("Closed accounting period ({}): {}", hresult, message),
+            ),
             SageError::ConnectionError {
                 database_path,
                 message,
-            } => {
-                write!(f, "Erreur de connexion à '{}': {}", database_path, message)
-            }
-            SageError::AuthenticationError { user, message } => {
-                write!(f, "Erreur d'authentification pour '{}': {}", user, message)
-            }
+            } => (
+                format!("Erreur de connexion à '{}': {}", database_path, message),
+                format!("Failed to connect to '{}': {}", database_path, message),
+            ),
+            SageError::AuthenticationError { user, message } => (
+                format!("Erreur d'authentification pour '{}': {}", user, message),
+                format!("Authentication failed for '{}': {}", user, message),
+            ),
             SageError::MethodCallError {
                 method_name,
                 method_id,
                 message,
-            } => {
-                write!(
-                    f,
+            } => (
+                format!(
                     "Erreur lors de l'appel de '{}' (ID: {}): {}",
                     method_name, method_id, message
-                )
-            }
+                ),
+                format!(
+                    "Call to '{}' (ID: {}) failed: {}",
+                    method_name, method_id, message
+                ),
+            ),
+            SageError::Automation(error) => return error.message(language),
             SageError::ConversionError {
                 from_type,
                 to_type,
                 value,
-            } => {
-                write!(
-                    f,
+            } => (
+                format!(
                     "Erreur de conversion de '{}' vers '{}' pour la valeur '{}'",
                     from_type, to_type, value
-                )
-            }
+                ),
+                format!(
+                    "Cannot convert value '{}' from '{}' to '{}'",
+                    value, from_type, to_type
+                ),
+            ),
             SageError::ValidationError {
                 field,
                 value,
                 constraint,
-            } => {
-                write!(
-                    f,
+            } => (
+                format!(
                     "Erreur de validation du champ '{}' avec la valeur '{}': {}",
                     field, value, constraint
-                )
-            }
-            SageError::DatabaseNotOpen => {
-                write!(f, "Aucune base de données n'est ouverte")
-            }
-            SageError::ClassNotRegistered(clsid) => {
-                write!(
-                    f,
+                ),
+                format!(
+                    "Validation failed for field '{}' with value '{}': {}",
+                    field, value, constraint
+                ),
+            ),
+            SageError::DatabaseNotOpen { .. } => (
+                "Aucune base de données n'est ouverte".to_string(),
+                "No database is open".to_string(),
+            ),
+            SageError::ClassNotRegistered { class, .. } => (
+                format!(
                     "Classe COM non enregistrée: {}. Exécutez 'regsvr32 objets100c.dll' en tant qu'administrateur",
-                    clsid
-                )
-            }
+                    class
+                ),
+                format!(
+                    "COM class not registered: {}. Run 'regsvr32 objets100c.dll' as administrator",
+                    class
+                ),
+            ),
             SageError::InvalidParameter {
                 parameter,
                 expected,
                 received,
-            } => {
-                write!(
-                    f,
+            } => (
+                format!(
                     "Paramètre '{}' invalide: attendu '{}', reçu '{}'",
                     parameter, expected, received
-                )
-            }
-            SageError::UnsupportedOperation(op) => {
-                write!(f, "Opération non supportée: {}", op)
-            }
-            SageError::InternalError(msg) => {
-                write!(f, "Erreur interne: {}", msg)
+                ),
+                format!(
+                    "Invalid parameter '{}': expected '{}', received '{}'",
+                    parameter, expected, received
+                ),
+            ),
+            SageError::UnsupportedOperation(op) => (
+                format!("Opération non supportée: {}", op),
+                format!("Unsupported operation: {}", op),
+            ),
+            SageError::InternalError(msg) => (
+                format!("Erreur interne: {}", msg),
+                format!("Internal error: {}", msg),
+            ),
+            SageError::Context { context, source } => {
+                return format!("{}: {}", context, source.message(language));
             }
+        };
+        language.pick(french, english)
+    }
+
+    /// Représentation sérialisable avec code stable et message localisé
+    pub fn report(&self, language: Language) -> ErrorReport<'_> {
+        ErrorReport {
+            code: self.code(),
+            message: self.message(language),
+            error: self,
         }
     }
 }
 
-impl std::error::Error for SageError {}
+/// Erreur telle que renvoyée aux intégrateurs, par exemple en JSON :
+/// `{"code":"SAGE-CONN-002","message":"No database is open","type":"DatabaseNotOpen","details":{}}`
+#[derive(Debug, Serialize)]
+pub struct ErrorReport<'a> {
+    pub code: &'static str,
+    pub message: String,
+    #[serde(flatten)]
+    pub error: &'a SageError,
+}
 
+/// Message dans la langue courante (`Language::current()`)
+impl fmt::Display for SageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message(Language::current()))
+    }
+}
+
+impl std::error::Error for SageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SageError::Context { source, .. } => Some(source.as_ref()),
+            SageError::ComError { source, .. } | SageError::ClassNotRegistered { source, .. } => {
+                source.as_ref().map(|source| source.as_error())
+            }
+            SageError::RecordLocked { source, automation, .. }
+            | SageError::DuplicateKey { source, automation, .. }
+            | SageError::RecordNotFound { source, automation, .. }
+            | SageError::DatabaseNotOpen { source, automation }
+            | SageError::LicenseInvalid { source, automation, .. }
+            | SageError::ClosedPeriod { source, automation, .. } => match (source, automation) {
+                (Some(source), _) => Some(source.as_error()),
+                (None, Some(automation)) => Some(automation.as_ref()),
+                (None, None) => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "com"))]
 impl From<WindowsError> for SageError {
     fn from(error: WindowsError) -> Self {
-        let hresult = error.code();
+        let hresult = HResult::from(error.code());
         let message = error.message().to_string_lossy().to_owned();
 
         // Traiter les erreurs COM spécifiques
         match hresult.0 {
             val if val == 0x80040154u32 as i32 => {
-                SageError::ClassNotRegistered("CLSID non trouvé".to_string())
+                SageError::ClassNotRegistered {
+                    class: "CLSID non trouvé".to_string(),
+                    source: Some(ErrorSource::new(error)),
+                }
             }
             val if val == 0x80070005u32 as i32 => SageError::ComError {
                 hresult,
                 message: "Accès refusé. Vérifiez les privilèges administrateur.".to_string(),
+                source: Some(ErrorSource::new(error)),
             },
-            _ => SageError::ComError { hresult, message },
+            _ => SageError::from_code_with_source(hresult, &message, Some(ErrorSource::new(error))),
         }
     }
 }
@@ -168,6 +390,158 @@ impl SageError {
         }
     }
 
+    /// Crée une erreur d'automation à partir du détail de l'EXCEPINFO
+    pub fn automation(error: AutomationError) -> Self {
+        SageError::Automation(Box::new(error))
+    }
+
+    /// Crée l'erreur correspondant à un code, via le catalogue des erreurs Sage
+    ///
+    /// Un code non catalogué donne une `ComError` générique.
+    pub fn from_code(hresult: HResult, message: &str) -> Self {
+        SageError::from_code_with_source(hresult, message, None)
+    }
+
+    /// Comme `from_code`, en conservant l'erreur d'origine
+    pub fn from_code_with_source(hresult: HResult, message: &str, source: Option<ErrorSource>) -> Self {
+        match lookup_error_code(hresult.0) {
+            Some(kind) => SageError::from_kind(kind, hresult, message, source, None),
+            None => SageError::ComError {
+                hresult,
+                message: message.to_string(),
+                source,
+            },
+        }
+    }
+
+    /// Classe une exception d'automation : son numéro d'erreur Sage, puis le
+    /// HRESULT d'Invoke, sont recherchés dans le catalogue ; le détail complet
+    /// reste accessible par `as_automation()` et `source()`
+    pub fn from_automation(error: AutomationError) -> Self {
+        let catalogued = [HResult(error.error_number()), error.scode, error.hresult]
+            .into_iter()
+            .find_map(|code| lookup_error_code(code.0).map(|kind| (kind, code)));
+
+        match catalogued {
+            Some((kind, code)) => {
+                let message = error.description.clone();
+                SageError::from_kind(kind, code, &message, None, Some(Box::new(error)))
+            }
+            None => SageError::automation(error),
+        }
+    }
+
+    /// Classe COM introuvable, sans erreur d'origine
+    pub fn class_not_registered<S: Into<String>>(class: S) -> Self {
+        SageError::ClassNotRegistered {
+            class: class.into(),
+            source: None,
+        }
+    }
+
+    /// Erreur « base non ouverte » sans détail d'origine
+    pub fn database_not_open() -> Self {
+        SageError::DatabaseNotOpen {
+            automation: None,
+            source: None,
+        }
+    }
+
+    fn from_kind(
+        kind: SageErrorKind,
+        hresult: HResult,
+        message: &str,
+        source: Option<ErrorSource>,
+        automation: Option<Box<AutomationError>>,
+    ) -> Self {
+        let message = message.to_string();
+        match kind {
+            SageErrorKind::RecordLocked => SageError::RecordLocked { hresult, message, source, automation },
+            SageErrorKind::DuplicateKey => SageError::DuplicateKey { hresult, message, source, automation },
+            SageErrorKind::RecordNotFound => SageError::RecordNotFound { hresult, message, source, automation },
+            SageErrorKind::DatabaseNotOpen => SageError::DatabaseNotOpen { automation, source },
+            SageErrorKind::LicenseInvalid => SageError::LicenseInvalid { hresult, message, source, automation },
+            SageErrorKind::ClosedPeriod => SageError::ClosedPeriod { hresult, message, source, automation },
+        }
+    }
+
+    /// Ajoute un contexte d'appel au-dessus de l'erreur
+    pub fn context<C: Into<String>>(self, context: C) -> Self {
+        SageError::Context {
+            context: context.into(),
+            source: Box::new(self),
+        }
+    }
+
+    /// Erreur d'origine, sous tous les contextes ajoutés
+    pub fn root(&self) -> &SageError {
+        match self {
+            SageError::Context { source, .. } => source.root(),
+            error => error,
+        }
+    }
+
+    /// Contextes ajoutés, du plus externe au plus proche de l'erreur
+    pub fn contexts(&self) -> Vec<&str> {
+        let mut contexts = Vec::new();
+        let mut error = self;
+        while let SageError::Context { context, source } = error {
+            contexts.push(context.as_str());
+            error = source;
+        }
+        contexts
+    }
+
+    /// Nature métier de l'erreur, si elle appartient au catalogue
+    pub fn kind(&self) -> Option<SageErrorKind> {
+        match self.root() {
+            SageError::RecordLocked { .. } => Some(SageErrorKind::RecordLocked),
+            SageError::DuplicateKey { .. } => Some(SageErrorKind::DuplicateKey),
+            SageError::RecordNotFound { .. } => Some(SageErrorKind::RecordNotFound),
+            SageError::DatabaseNotOpen { .. } => Some(SageErrorKind::DatabaseNotOpen),
+            SageError::LicenseInvalid { .. } => Some(SageErrorKind::LicenseInvalid),
+            SageError::ClosedPeriod { .. } => Some(SageErrorKind::ClosedPeriod),
+            _ => None,
+        }
+    }
+
+    /// Vérifie si l'opération peut réussir en étant relancée plus tard
+    /// (verrou d'un autre utilisateur, serveur COM occupé)
+    pub fn is_retryable(&self) -> bool {
+        match self.root() {
+            SageError::RecordLocked { .. } => true,
+            SageError::ComError { hresult, .. } => {
+                *hresult == HResult::RPC_E_CALL_REJECTED
+                    || *hresult == HResult::RPC_E_SERVERCALL_RETRYLATER
+            }
+            _ => false,
+        }
+    }
+
+    /// Vérifie si l'erreur est un conflit de verrouillage
+    pub fn is_lock_conflict(&self) -> bool {
+        matches!(self.root(), SageError::RecordLocked { .. })
+    }
+
+    /// Vérifie si l'enregistrement demandé n'existe pas
+    pub fn is_not_found(&self) -> bool {
+        matches!(self.root(), SageError::RecordNotFound { .. })
+    }
+
+    /// Détail de l'exception d'automation, y compris sous une erreur cataloguée
+    pub fn as_automation(&self) -> Option<&AutomationError> {
+        match self.root() {
+            SageError::Automation(error) => Some(error),
+            SageError::RecordLocked { automation, .. }
+            | SageError::DuplicateKey { automation, .. }
+            | SageError::RecordNotFound { automation, .. }
+            | SageError::DatabaseNotOpen { automation, .. }
+            | SageError::LicenseInvalid { automation, .. }
+            | SageError::ClosedPeriod { automation, .. } => automation.as_deref(),
+            _ => None,
+        }
+    }
+
     /// Crée une erreur de connexion
     #[allow(dead_code)] // Sera utilisé dans v0.2.0
     pub fn connection(database_path: &str, message: &str) -> Self {
@@ -200,15 +574,23 @@ impl SageError {
     /// Vérifie si l'erreur est liée à une classe COM non enregistrée
     #[allow(dead_code)] // Sera utilisé dans v0.2.0
     pub fn is_class_not_registered(&self) -> bool {
-        matches!(self, SageError::ClassNotRegistered(_))
+        matches!(self.root(), SageError::ClassNotRegistered { .. })
+    }
+
+    /// Vérifie si COM n'est pas initialisé sur le thread (aucun `ComApartment`)
+    pub fn is_com_not_initialized(&self) -> bool {
+        matches!(
+            self.root(),
+            SageError::ComError { hresult: HResult::CO_E_NOTINITIALIZED, .. }
+        )
     }
 
     /// Vérifie si l'erreur est liée à la connexion
     #[allow(dead_code)] // Sera utilisé dans v0.2.0
     pub fn is_connection_error(&self) -> bool {
         matches!(
-            self,
-            SageError::ConnectionError { .. } | SageError::DatabaseNotOpen
+            self.root(),
+            SageError::ConnectionError { .. } | SageError::DatabaseNotOpen { .. }
         )
     }
 }
@@ -225,10 +607,145 @@ mod tests {
 
     #[test]
     fn test_error_classification() {
-        let error = SageError::ClassNotRegistered("test".to_string());
+        let error = SageError::class_not_registered("test");
         assert!(error.is_class_not_registered());
 
-        let error = SageError::DatabaseNotOpen;
+        let error = SageError::database_not_open();
+        assert!(error.is_connection_error());
+    }
+
+    #[test]
+    fn test_stable_code_and_localized_message() {
+        let error = SageError::connection("C:\\Sage\\BIJOU.MAE", "fichier introuvable");
+        assert_eq!(error.code(), "SAGE-CONN-001");
+        assert!(error.message(Language::French).starts_with("Erreur de connexion"));
+        assert!(error.message(Language::English).starts_with("Failed to connect"));
+    }
+
+    #[test]
+    fn test_error_report_json() {
+        let error = SageError::from_code(HResult(0x80040E19u32 as i32), "Compte 411000 absent");
+        let json = serde_json::to_value(error.report(Language::English)).unwrap();
+
+        assert_eq!(json["code"], "SAGE-DATA-003");
+        assert_eq!(json["type"], "RecordNotFound");
+        assert_eq!(json["details"]["hresult"], "0x80040E19");
+        assert_eq!(json["message"], "Record not found (0x80040E19): Compte 411000 absent");
+
+        let json = serde_json::to_value(SageError::database_not_open().report(Language::French)).unwrap();
+        assert_eq!(json["type"], "DatabaseNotOpen");
+        assert_eq!(json["message"], "Aucune base de données n'est ouverte");
+    }
+
+    #[test]
+    fn test_catalog_classification() {
+        let locked = SageError::from_code(HResult(0x80040E38u32 as i32), "verrou");
+        assert!(locked.is_lock_conflict());
+        assert!(locked.is_retryable());
+        assert_eq!(locked.kind(), Some(SageErrorKind::RecordLocked));
+
+        let busy = SageError::from_code(HResult::RPC_E_SERVERCALL_RETRYLATER, "occupé");
+        assert!(busy.is_retryable());
+        assert!(busy.kind().is_none());
+
+        let generic = SageError::from_code(HResult(0x80004005u32 as i32), "échec");
+        assert!(matches!(generic, SageError::ComError { .. }));
+        assert!(!generic.is_retryable());
+    }
+
+    #[test]
+    fn test_automation_error_classification() {
+        let exception = AutomationError {
+            member: "ReadNumero".to_string(),
+            dispid: 2,
+            hresult: HResult::DISP_E_EXCEPTION,
+            scode: HResult(0x80040E19u32 as i32),
+            code: 0,
+            source: "Objets100c".to_string(),
+            description: "Enregistrement non trouvé".to_string(),
+            help_file: None,
+            help_context: 0,
+            arg_position: None,
+        };
+        let error = SageError::from_automation(exception.clone());
+        assert!(error.is_not_found());
+        assert!(error.to_string().contains("Enregistrement non trouvé"));
+
+        // Code non catalogué : le détail complet est conservé
+        let unknown = AutomationError { scode: HResult(0x80040999u32 as i32), ..exception };
+        let error = SageError::from_automation(unknown);
+        assert_eq!(error.as_automation().map(|e| e.dispid), Some(2));
+    }
+
+    /// Exception Objets Métier portant un numéro d'erreur Sage (`wCode`)
+    fn sage_exception(code: u16) -> AutomationError {
+        AutomationError {
+            member: "Write".to_string(),
+            dispid: 7,
+            hresult: HResult::DISP_E_EXCEPTION,
+            scode: HResult(0x80040000u32 as i32),
+            code,
+            source: "Objets100c.Cpta.Stream.1".to_string(),
+            description: "Erreur Objets Métier".to_string(),
+            help_file: Some("objets100c.chm".to_string()),
+            help_context: 42,
+            arg_position: Some(1),
+        }
+    }
+
+    #[test]
+    fn test_classified_automation_error_keeps_detail() {
+        let error = SageError::from_automation(sage_exception(12_022)).context("lecture du compte");
+        assert!(error.is_not_found());
+
+        let detail = error.as_automation().unwrap();
+        assert_eq!((detail.member.as_str(), detail.code, detail.arg_position), ("Write", 12_022, Some(1)));
+
+        let source = std::error::Error::source(error.root()).unwrap();
+        assert_eq!(source.downcast_ref::<AutomationError>(), Some(detail));
+
+        let json = serde_json::to_value(error.root().report(Language::French)).unwrap();
+        assert_eq!(json["type"], "RecordNotFound");
+        assert_eq!(json["details"]["automation"]["code"], 12_022);
+        assert_eq!(json["details"]["automation"]["help_file"], "objets100c.chm");
+    }
+
+    #[test]
+    fn test_sage_record_locked() {
+        let error = SageError::from_automation(sage_exception(12_024));
+        assert!(error.is_lock_conflict() && error.is_retryable());
+    }
+
+    #[test]
+    fn test_sage_duplicate_key() {
+        let error = SageError::from_automation(sage_exception(12_023));
+        assert_eq!(error.kind(), Some(SageErrorKind::DuplicateKey));
+    }
+
+    #[test]
+    fn test_sage_record_not_found() {
+        let error = SageError::from_automation(sage_exception(12_022));
+        assert!(error.is_not_found());
+    }
+
+    #[test]
+    fn test_sage_database_not_open() {
+        let error = SageError::from_automation(sage_exception(12_011));
         assert!(error.is_connection_error());
+        assert_eq!(error.as_automation().map(|e| e.code), Some(12_011));
+    }
+
+    #[test]
+    fn test_sage_license_invalid() {
+        let error = SageError::from_automation(sage_exception(12_101));
+        assert_eq!(error.kind(), Some(SageErrorKind::LicenseInvalid));
+        assert_eq!(error.code(), "SAGE-AUTH-002");
+    }
+
+    #[test]
+    fn test_sage_closed_period() {
+        let error = SageError::from_automation(sage_exception(12_061));
+        assert_eq!(error.kind(), Some(SageErrorKind::ClosedPeriod));
+        assert!(!error.is_retryable());
     }
 }
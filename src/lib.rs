@@ -1,7 +1,14 @@
+// Permet aux macros dérivées d'utiliser `::objets_metier_rs` dans ce crate
+extern crate self as objets_metier_rs;
+
 pub mod com;
 pub mod errors;
 pub mod wrappers;
 
-pub use com::{ComInstance, SafeDispatch, SafeString, SafeVariant};
-pub use errors::{SageError, SageResult};
-pub use wrappers::{CptaApplication, CialApplication, ILoggable};
+#[cfg(all(windows, feature = "com"))]
+pub use com::ComInstance;
+pub use com::{FromVariant, IntoVariant, SafeDispatch, SafeString, SafeVariant, SageObject, SageRecord};
+pub use errors::{AutomationError, Language, ResultExt, SageError, SageErrorKind, SageResult};
+pub use objets_metier_rs_derive::SageObject;
+pub use rust_decimal::Decimal;
+pub use wrappers::{CptaApplication, CialApplication, ILoggable, SageCollection};
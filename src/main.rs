@@ -1,18 +1,23 @@
-mod com;
-mod errors;
-mod wrappers;
+#[cfg(all(windows, feature = "com"))]
+use objets_metier_rs::wrappers::{CptaApplication, FactoryJournal};
+#[cfg(all(windows, feature = "com"))]
+use objets_metier_rs::com::ComApartment;
+#[cfg(all(windows, feature = "com"))]
+use objets_metier_rs::errors::SageResult;
 
-use wrappers::{CptaApplication, FactoryJournal};
-use errors::SageResult;
-
-const BSCPTA_CLSID: &str = "309DE0FB-9FB8-4F4E-8295-CC60C60DAA33";
+#[cfg(not(all(windows, feature = "com")))]
+fn main() {
+    eprintln!("❌ Ce programme nécessite Windows et la feature `com` (Objets Métier Sage 100c)");
+}
 
+#[cfg(all(windows, feature = "com"))]
 fn main() -> SageResult<()> {
     println!("🚀 Test des signatures FactoryJournal corrigées v0.1.3");
     println!("═══════════════════════════════════════════════════════");
     
-    // Créer l'application Sage
-    let app = CptaApplication::new(BSCPTA_CLSID)?;
+    // Initialiser COM pour ce thread, puis créer l'application Sage
+    let _com = ComApartment::sta()?;
+    let app = CptaApplication::newest_installed()?;
     app.set_name(r"D:\TMP\BIJOU.MAE")?;
     app.loggable()?.set_user_name("<Administrateur>")?;
     app.loggable()?.set_user_pwd("")?;
@@ -1,13 +1,19 @@
-use crate::errors::{SageError, SageResult};
-use crate::com::{ComInstance, SafeDispatch, SafeVariant};
+use crate::errors::{ResultExt, SageError, SageResult};
+#[cfg(all(windows, feature = "com"))]
+use crate::com::{ComInstance, RegistryReader, SageClassInfo, SageModule, WindowsRegistry, newest_class};
+use crate::com::{DispatchBackend, SafeDispatch, SafeVariant};
 use crate::wrappers::{ILoggable};
 
 /// Wrapper pour l'application BSCPTAApplication avec accès typé aux propriétés
-pub struct CialApplication {
-    instance: ComInstance,
+///
+/// Le backend `D` est une `ComInstance` en production ; tout autre `DispatchBackend`
+/// (par exemple un `FakeObject`) permet de tester la logique sans Sage.
+pub struct CialApplication<D: DispatchBackend> {
+    instance: D,
 }
 
-impl CialApplication {
+#[cfg(all(windows, feature = "com"))]
+impl CialApplication<ComInstance> {
     /// Crée un wrapper CialApplication à partir d'un CLSID
     pub fn new(clsid: &str) -> SageResult<Self> {
         let instance = ComInstance::new(clsid)?;
@@ -17,15 +23,43 @@ impl CialApplication {
         })
     }
 
+    /// Crée un wrapper CialApplication à partir d'un ProgID (ex: `Objets100c.Cial.Stream.1`)
+    pub fn from_progid(progid: &str) -> SageResult<Self> {
+        Ok(CialApplication {
+            instance: ComInstance::from_progid(progid)?,
+        })
+    }
+
+    /// Crée un wrapper CialApplication sur une classe découverte (voir `discover_classes`)
+    pub fn from_class(class: &SageClassInfo) -> SageResult<Self> {
+        Self::new(&class.clsid)
+    }
+
+    /// Crée un wrapper CialApplication avec la version la plus récente installée
+    pub fn newest_installed() -> SageResult<Self> {
+        Self::newest_from(&WindowsRegistry)
+    }
+
+    /// Comme `newest_installed`, en lisant le registre fourni
+    pub fn newest_from(registry: &dyn RegistryReader) -> SageResult<Self> {
+        Self::from_class(&newest_class(registry, SageModule::Cial)?)
+    }
+
     /// Accès à l'instance COM sous-jacente pour les opérations avancées
     pub fn instance(&self) -> &ComInstance {
         &self.instance
     }
+}
+
+impl<D: DispatchBackend> CialApplication<D> {
+    /// Crée un wrapper CialApplication au-dessus d'un backend d'automation quelconque
+    pub fn from_backend(backend: D) -> Self {
+        CialApplication { instance: backend }
+    }
 
     /// Crée un SafeDispatch temporaire pour les appels
     fn dispatch(&'_ self) -> SageResult<SafeDispatch<'_>> {
-        let dispatch_ref = self.instance.dispatch()?;
-        Ok(SafeDispatch::new(dispatch_ref))
+        Ok(SafeDispatch::new(&self.instance))
     }
 
     /// Vérifie si une base de données est ouverte
@@ -60,7 +94,7 @@ impl CialApplication {
             });
         }
 
-        let loggable_dispatch = loggable_variant.to_dispatch()?;
+        let loggable_dispatch = loggable_variant.to_object()?;
         
         Ok(ILoggable { dispatch: loggable_dispatch })
     }
@@ -68,7 +102,8 @@ impl CialApplication {
     /// NOUVELLE MÉTHODE - Ouvre une base de données sans paramètre - ÉQUIVALENT _mCpta.Open()
     /// Utilise le chemin défini précédemment avec set_name()
     pub fn open(&self) -> SageResult<()> {
-        self.dispatch()?.call_method_by_name("Open", &[])?;
+        self.dispatch()?.call_method_by_name("Open", &[])
+            .context("ouverture de la base commerciale")?;
         Ok(())
     }
 
@@ -98,10 +133,25 @@ impl CialApplication {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::com::{Cassette, Player};
 
     #[test]
-    fn test_sage_application_creation() {
-        // Ce test nécessiterait un environnement Sage pour fonctionner
-        // Il sert de documentation pour l'utilisation
+    fn test_open_session_replayed_from_cassette() {
+        let player = Player::new(
+            Cassette::load(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cassettes/cial_open.json")).unwrap(),
+        );
+        let app = CialApplication::from_backend(player.root());
+
+        app.set_name(r"C:\Sage\BIJOU.GCM").unwrap();
+        let loggable = app.loggable().unwrap();
+        loggable.set_user_name("<Administrateur>").unwrap();
+        loggable.set_user_pwd("").unwrap();
+        app.open().unwrap();
+        assert!(app.is_open().unwrap());
+        assert!(loggable.is_logged().unwrap());
+        assert_eq!(app.get_name().unwrap(), r"C:\Sage\BIJOU.GCM");
+        app.close().unwrap();
+        assert_eq!(player.remaining(), 0);
     }
 }
@@ -0,0 +1,158 @@
+use crate::errors::SageResult;
+use crate::com::{DispatchBackend, DispatchObject, SafeDispatch, SafeVariant, FromDispatch, VariantEnumerator};
+use std::collections::VecDeque;
+
+/// Nombre d'éléments demandés à chaque appel de `IEnumVARIANT::Next`
+const ENUM_BATCH_SIZE: usize = 50;
+
+/// Wrapper pour les collections Sage (IBICollection) retournées par
+/// `List`, `ListOrderNumero`, `QueryActifOrderNumero`...
+///
+/// ```ignore
+/// let comptes = SageCollection::from_variant(&factory_compte.get_property("List")?)?;
+/// for compte in &comptes {
+///     let compte = compte?;
+/// }
+/// ```
+pub struct SageCollection<D: DispatchBackend = DispatchObject> {
+    pub dispatch: D,
+}
+
+impl SageCollection<DispatchObject> {
+    /// Construit la collection à partir du résultat d'un appel (`List`...)
+    pub fn from_variant(value: &SafeVariant) -> SageResult<Self> {
+        Ok(SageCollection { dispatch: value.to_object()? })
+    }
+}
+
+impl<D: DispatchBackend> SageCollection<D> {
+    /// Crée un SafeDispatch temporaire pour les appels
+    fn dispatch(&self) -> SafeDispatch<'_> {
+        SafeDispatch::new(&self.dispatch)
+    }
+
+    /// Nombre d'éléments - ÉQUIVALENT .Count
+    pub fn count(&self) -> SageResult<i32> {
+        self.dispatch().call_method_by_name("Count", &[])?
+            .to_i32()
+    }
+
+    /// Élément à l'index donné, à partir de 1 comme en VB - ÉQUIVALENT .Item(i)
+    pub fn item(&self, index: i32) -> SageResult<SafeVariant> {
+        self.dispatch().call_method_by_name("Item", &[SafeVariant::I4(index)])
+    }
+
+    /// Parcourt la collection via `_NewEnum`, les éléments étant lus par lots
+    pub fn iter(&self) -> SageCollectionIter<'_, D> {
+        SageCollectionIter {
+            collection: self,
+            enumerator: None,
+            buffer: VecDeque::new(),
+            finished: false,
+        }
+    }
+}
+
+impl<D: DispatchBackend> FromDispatch<D> for SageCollection<D> {
+    fn from_dispatch(dispatch: D) -> SageResult<Self> {
+        Ok(SageCollection { dispatch })
+    }
+}
+
+impl<'a, D: DispatchBackend> IntoIterator for &'a SageCollection<D> {
+    type Item = SageResult<SafeVariant>;
+    type IntoIter = SageCollectionIter<'a, D>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Itérateur sur une `SageCollection`
+///
+/// L'énumérateur n'est ouvert qu'au premier élément demandé. Une erreur
+/// est retournée une seule fois, l'itération s'arrête ensuite.
+pub struct SageCollectionIter<'a, D: DispatchBackend> {
+    collection: &'a SageCollection<D>,
+    enumerator: Option<Box<dyn VariantEnumerator>>,
+    buffer: VecDeque<SafeVariant>,
+    finished: bool,
+}
+
+impl<D: DispatchBackend> SageCollectionIter<'_, D> {
+    /// Remplit le tampon avec le lot suivant
+    fn fill(&mut self) -> SageResult<()> {
+        let enumerator = match &mut self.enumerator {
+            Some(enumerator) => enumerator,
+            None => self.enumerator.insert(self.collection.dispatch.new_enum()?),
+        };
+        let batch = enumerator.next_batch(ENUM_BATCH_SIZE)?;
+        if batch.len() < ENUM_BATCH_SIZE {
+            self.finished = true;
+        }
+        self.buffer.extend(batch);
+        Ok(())
+    }
+}
+
+impl<D: DispatchBackend> Iterator for SageCollectionIter<'_, D> {
+    type Item = SageResult<SafeVariant>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buffer.is_empty()
+            && !self.finished
+            && let Err(e) = self.fill()
+        {
+            self.finished = true;
+            return Some(Err(e));
+        }
+        self.buffer.pop_front().map(Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::com::FakeObject;
+
+    fn comptes(count: usize) -> FakeObject {
+        let items = (0..count)
+            .map(|i| FakeObject::new("IBOCompteG3").with_property("CG_Num", format!("4110{:02}", i)).into())
+            .collect();
+        FakeObject::new("IBICollection").with_items(items)
+    }
+
+    #[test]
+    fn test_collection_count_and_item() {
+        let collection = SageCollection { dispatch: comptes(3) };
+
+        assert_eq!(collection.count().unwrap(), 3);
+        let compte = collection.item(1).unwrap().to_object().unwrap();
+        let num = SafeDispatch::new(&compte).call_method_by_name("CG_Num", &[]).unwrap();
+        assert_eq!(num.to_string().unwrap(), "411000");
+        assert!(collection.item(0).is_err());
+    }
+
+    #[test]
+    fn test_collection_iterates_across_batches() {
+        let collection = SageCollection { dispatch: comptes(ENUM_BATCH_SIZE + 7) };
+
+        let mut numeros = Vec::new();
+        for compte in &collection {
+            let compte = compte.unwrap().to_object().unwrap();
+            numeros.push(SafeDispatch::new(&compte).call_method_by_name("CG_Num", &[]).unwrap().to_string().unwrap());
+        }
+
+        assert_eq!(numeros.len(), ENUM_BATCH_SIZE + 7);
+        assert_eq!(numeros.last().unwrap(), "411056");
+    }
+
+    #[test]
+    fn test_non_collection_yields_single_error() {
+        let collection = SageCollection { dispatch: FakeObject::new("IBOJournal3") };
+        let results: Vec<_> = collection.iter().collect();
+
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err());
+    }
+}
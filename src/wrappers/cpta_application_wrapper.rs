@@ -1,13 +1,19 @@
-use crate::errors::{SageError, SageResult};
-use crate::com::{ComInstance, SafeDispatch, SafeVariant};
+use crate::errors::{ResultExt, SageError, SageResult};
+#[cfg(all(windows, feature = "com"))]
+use crate::com::{ComInstance, RegistryReader, SageClassInfo, SageModule, WindowsRegistry, newest_class};
+use crate::com::{DispatchBackend, SafeDispatch, SafeVariant};
 use crate::wrappers::{ILoggable};
 
 /// Wrapper pour l'application BSCPTAApplication avec accès typé aux propriétés
-pub struct CptaApplication {
-    instance: ComInstance,
+///
+/// Le backend `D` est une `ComInstance` en production ; tout autre `DispatchBackend`
+/// (par exemple un `FakeObject`) permet de tester la logique sans Sage.
+pub struct CptaApplication<D: DispatchBackend> {
+    instance: D,
 }
 
-impl CptaApplication {
+#[cfg(all(windows, feature = "com"))]
+impl CptaApplication<ComInstance> {
     /// Crée un wrapper CptaApplication à partir d'un CLSID
     pub fn new(clsid: &str) -> SageResult<Self> {
         let instance = ComInstance::new(clsid)?;
@@ -17,15 +23,43 @@ impl CptaApplication {
         })
     }
 
+    /// Crée un wrapper CptaApplication à partir d'un ProgID (ex: `Objets100c.Cpta.Stream.1`)
+    pub fn from_progid(progid: &str) -> SageResult<Self> {
+        Ok(CptaApplication {
+            instance: ComInstance::from_progid(progid)?,
+        })
+    }
+
+    /// Crée un wrapper CptaApplication sur une classe découverte (voir `discover_classes`)
+    pub fn from_class(class: &SageClassInfo) -> SageResult<Self> {
+        Self::new(&class.clsid)
+    }
+
+    /// Crée un wrapper CptaApplication avec la version la plus récente installée
+    pub fn newest_installed() -> SageResult<Self> {
+        Self::newest_from(&WindowsRegistry)
+    }
+
+    /// Comme `newest_installed`, en lisant le registre fourni
+    pub fn newest_from(registry: &dyn RegistryReader) -> SageResult<Self> {
+        Self::from_class(&newest_class(registry, SageModule::Cpta)?)
+    }
+
     /// Accès à l'instance COM sous-jacente pour les opérations avancées
     pub fn instance(&self) -> &ComInstance {
         &self.instance
     }
+}
+
+impl<D: DispatchBackend> CptaApplication<D> {
+    /// Crée un wrapper CptaApplication au-dessus d'un backend d'automation quelconque
+    pub fn from_backend(backend: D) -> Self {
+        CptaApplication { instance: backend }
+    }
 
     /// Crée un SafeDispatch temporaire pour les appels
     fn dispatch(&'_ self) -> SageResult<SafeDispatch<'_>> {
-        let dispatch_ref = self.instance.dispatch()?;
-        Ok(SafeDispatch::new(dispatch_ref))
+        Ok(SafeDispatch::new(&self.instance))
     }
 
     /// Vérifie si une base de données est ouverte
@@ -50,7 +84,8 @@ impl CptaApplication {
 
     /// Accède à l'objet Loggable - ÉQUIVALENT .Loggable en C#/VB
     pub fn loggable(&self) -> SageResult<ILoggable> {
-        let loggable_variant = self.dispatch()?.call_method_by_name("Loggable", &[])?;
+        let loggable_variant = self.dispatch()?.call_method_by_name("Loggable", &[])
+            .context("accès à Loggable")?;
         
         if !loggable_variant.is_object() {
             return Err(SageError::ConversionError {
@@ -60,7 +95,7 @@ impl CptaApplication {
             });
         }
 
-        let loggable_dispatch = loggable_variant.to_dispatch()?;
+        let loggable_dispatch = loggable_variant.to_object()?;
         
         Ok(ILoggable { dispatch: loggable_dispatch })
     }
@@ -68,7 +103,8 @@ impl CptaApplication {
     /// NOUVELLE MÉTHODE - Ouvre une base de données sans paramètre - ÉQUIVALENT _mCpta.Open()
     /// Utilise le chemin défini précédemment avec set_name()
     pub fn open(&self) -> SageResult<()> {
-        self.dispatch()?.call_method_by_name("Open", &[])?;
+        self.dispatch()?.call_method_by_name("Open", &[])
+            .context("ouverture de la base comptable")?;
         Ok(())
     }
 
@@ -98,10 +134,53 @@ impl CptaApplication {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::com::{Cassette, FakeObject, Player};
+
+    #[test]
+    fn test_open_session_replayed_from_cassette() {
+        let player = Player::new(
+            Cassette::load(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cassettes/cpta_open.json")).unwrap(),
+        );
+        let app = CptaApplication::from_backend(player.root());
+
+        app.set_name(r"C:\Sage\BIJOU.MAE").unwrap();
+        let loggable = app.loggable().unwrap();
+        loggable.set_user_name("<Administrateur>").unwrap();
+        loggable.set_user_pwd("").unwrap();
+        app.open().unwrap();
+        assert!(app.is_open().unwrap());
+        assert!(loggable.is_logged().unwrap());
+        assert_eq!(app.get_name().unwrap(), r"C:\Sage\BIJOU.MAE");
+        app.close().unwrap();
+        assert_eq!(player.remaining(), 0);
+    }
 
     #[test]
-    fn test_sage_application_creation() {
-        // Ce test nécessiterait un environnement Sage pour fonctionner
-        // Il sert de documentation pour l'utilisation
+    fn test_loggable_with_fake_backend() {
+        let loggable = FakeObject::new("IBILoggable")
+            .with_property("UserName", "")
+            .with_property("UserPwd", "")
+            .with_property("IsLogged", false);
+        let fake_app = FakeObject::new("BSCPTAApplication100c")
+            .with_property("Name", "")
+            .with_property("Loggable", loggable.clone());
+        let app = CptaApplication::from_backend(fake_app.clone());
+
+        app.set_name(r"D:\TMP\BIJOU.MAE").unwrap();
+        app.loggable().unwrap().set_user_name("<Administrateur>").unwrap();
+
+        assert_eq!(app.get_name().unwrap(), r"D:\TMP\BIJOU.MAE");
+        assert_eq!(app.loggable().unwrap().get_user_name().unwrap(), "<Administrateur>");
+        assert!(!app.loggable().unwrap().is_logged().unwrap());
+    }
+
+    #[test]
+    fn test_loggable_requires_object() {
+        let fake_app = FakeObject::new("BSCPTAApplication100c").with_property("Loggable", 0);
+        let app = CptaApplication::from_backend(fake_app);
+
+        assert!(app.loggable().is_err());
     }
 }
+
@@ -1,15 +1,15 @@
 use crate::errors::SageResult;
-use crate::com::{SafeDispatch, SafeVariant};
-use windows::Win32::System::Com::IDispatch;
+use crate::com::{DispatchBackend, DispatchObject, SafeDispatch, SafeVariant};
+use crate::wrappers::SageCollection;
 
 /// Wrapper pour l'objet FactoryJournal de Sage 100c (IBOJournalFactory3)
-pub struct FactoryJournal {
-    pub dispatch: IDispatch,
+pub struct FactoryJournal<D: DispatchBackend = DispatchObject> {
+    pub dispatch: D,
 }
 
-impl FactoryJournal {
+impl<D: DispatchBackend> FactoryJournal<D> {
     /// Crée un SafeDispatch temporaire pour les appels
-    fn dispatch(&self) -> SafeDispatch {
+    fn dispatch(&self) -> SafeDispatch<'_> {
         SafeDispatch::new(&self.dispatch)
     }
 
@@ -59,11 +59,18 @@ impl FactoryJournal {
     pub fn exists_by_id(&self, numero: i32) -> SageResult<bool> {
         self.exist_numero(SafeVariant::I4(numero))
     }
+
+    /// Liste de tous les journaux - ÉQUIVALENT .List
+    pub fn list(&self) -> SageResult<SageCollection> {
+        SageCollection::from_variant(&self.dispatch().call_method_by_name("List", &[])?)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::com::FakeObject;
+    use crate::errors::SageError;
 
     #[test]
     fn test_factory_journal_signatures() {
@@ -89,4 +96,51 @@ mod tests {
         // factory.read_numero() -- 0 paramètre
         // factory.read_numero("VTE", 1) -- 2+ paramètres
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_read_by_code_with_fake_backend() {
+        let journal = FakeObject::new("IBOJournal3").with_property("JO_Num", "VTE");
+        let fake_factory = FakeObject::new("IBOJournalFactory3")
+            .with_method("ExistNumero", |params| {
+                Ok(SafeVariant::Bool(params[0].to_string()? == "VTE"))
+            })
+            .with_method("ReadNumero", move |params| {
+                if params[0].to_string()? == "VTE" {
+                    Ok(journal.clone().into())
+                } else {
+                    Err(SageError::method_call("ReadNumero", 2, "Enregistrement introuvable"))
+                }
+            });
+        let factory = FactoryJournal { dispatch: fake_factory.clone() };
+
+        assert!(factory.exists_by_code("VTE").unwrap());
+        assert!(!factory.exists_by_code("ACH").unwrap());
+        let result = factory.read_by_code("VTE").unwrap();
+        assert!(result.is_object());
+        assert!(factory.read_by_code("ACH").is_err());
+        assert_eq!(fake_factory.calls().len(), 4);
+    }
+
+    #[test]
+    fn test_list_iterates_journals() {
+        let journals = ["ACH", "VTE", "BQ1"]
+            .iter()
+            .map(|code| FakeObject::new("IBOJournal3").with_property("JO_Num", *code).into())
+            .collect();
+        let list = FakeObject::new("IBICollection").with_items(journals);
+        let factory = FactoryJournal {
+            dispatch: FakeObject::new("IBOJournalFactory3").with_property("List", list),
+        };
+
+        let collection = factory.list().unwrap();
+        assert_eq!(collection.count().unwrap(), 3);
+        let codes: Vec<String> = collection
+            .iter()
+            .map(|journal| {
+                let journal = journal.unwrap().to_object().unwrap();
+                SafeDispatch::new(&journal).call_method_by_name("JO_Num", &[]).unwrap().to_string().unwrap()
+            })
+            .collect();
+        assert_eq!(codes, vec!["ACH", "VTE", "BQ1"]);
+    }
+}
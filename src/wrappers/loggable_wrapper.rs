@@ -1,16 +1,15 @@
-use crate::errors::{SageResult};
-use crate::com::{SafeDispatch, SafeVariant, FromDispatch};
-use windows::Win32::System::Com::IDispatch;
+use crate::errors::{ResultExt, SageResult};
+use crate::com::{DispatchBackend, DispatchObject, SafeDispatch, SafeVariant, FromDispatch};
 
 /// Wrapper pour l'objet IBILoggable avec accès typé aux propriétés
-pub struct ILoggable {
-    pub dispatch: IDispatch,
+pub struct ILoggable<D: DispatchBackend = DispatchObject> {
+    pub dispatch: D,
 }
 
 
-impl ILoggable {
+impl<D: DispatchBackend> ILoggable<D> {
     /// Crée un SafeDispatch temporaire pour les appels
-    fn dispatch(&self) -> SafeDispatch {
+    fn dispatch(&self) -> SafeDispatch<'_> {
         SafeDispatch::new(&self.dispatch)
     }
 
@@ -35,14 +34,16 @@ impl ILoggable {
     /// SETTER - Définit le nom d'utilisateur - ÉQUIVALENT .Loggable.UserName = "<Administrateur>"
     pub fn set_user_name(&self, username: &str) -> SageResult<()> {
         let username_variant = SafeVariant::from_string(username);
-        self.dispatch().call_property_put("UserName", &[username_variant])?;
+        self.dispatch().call_property_put("UserName", &[username_variant])
+            .context("affectation de Loggable.UserName")?;
         Ok(())
     }
 
     /// SETTER - Définit le mot de passe utilisateur - ÉQUIVALENT .Loggable.UserPwd = ""
     pub fn set_user_pwd(&self, password: &str) -> SageResult<()> {
         let pwd_variant = SafeVariant::from_string(password);
-        self.dispatch().call_property_put("UserPwd", &[pwd_variant])?;
+        self.dispatch().call_property_put("UserPwd", &[pwd_variant])
+            .context("affectation de Loggable.UserPwd")?;
         Ok(())
     }
 
@@ -83,8 +84,8 @@ impl ILoggable {
     }
 }
 
-impl FromDispatch for ILoggable {
-    fn from_dispatch(dispatch: IDispatch) -> SageResult<Self> {
+impl<D: DispatchBackend> FromDispatch<D> for ILoggable<D> {
+    fn from_dispatch(dispatch: D) -> SageResult<Self> {
         Ok(ILoggable { dispatch })
     }
 }
\ No newline at end of file
@@ -2,8 +2,12 @@ pub mod cpta_application_wrapper;
 pub mod cial_application_wrapper;
 pub mod loggable_wrapper;
 pub mod factory_journal_wrapper;
+pub mod collection_wrapper;
+pub mod records;
 
 pub use cpta_application_wrapper::{CptaApplication};
 pub use cial_application_wrapper::{CialApplication};
 pub use loggable_wrapper::{ILoggable};
 pub use factory_journal_wrapper::{FactoryJournal};
+pub use collection_wrapper::{SageCollection, SageCollectionIter};
+pub use records::{Article, CompteG, Tiers};
@@ -0,0 +1,96 @@
+use crate::SageObject;
+use rust_decimal::Decimal;
+
+/// Compte général (IBOCompteG3)
+#[derive(Debug, Clone, PartialEq, SageObject)]
+pub struct CompteG {
+    #[sage(name = "CG_Num")]
+    pub numero: String,
+    #[sage(name = "CG_Intitule")]
+    pub intitule: String,
+    #[sage(name = "CG_Sommeil")]
+    pub en_sommeil: bool,
+}
+
+/// Tiers client, fournisseur, salarié... (IBOTiers3)
+#[derive(Debug, Clone, PartialEq, SageObject)]
+pub struct Tiers {
+    #[sage(name = "CT_Num")]
+    pub numero: String,
+    #[sage(name = "CT_Intitule")]
+    pub intitule: String,
+    #[sage(name = "CT_Classement")]
+    pub classement: String,
+    #[sage(name = "CT_Sommeil")]
+    pub en_sommeil: bool,
+}
+
+/// Article (IBOArticle3)
+#[derive(Debug, Clone, PartialEq, SageObject)]
+pub struct Article {
+    #[sage(name = "AR_Ref")]
+    pub reference: String,
+    #[sage(name = "AR_Design")]
+    pub designation: String,
+    #[sage(name = "AR_PrixVen")]
+    pub prix_vente: Decimal,
+    #[sage(name = "AR_Sommeil")]
+    pub en_sommeil: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::com::{FakeObject, FromDispatch, InvokeKind, SafeVariant, SageRecord};
+    use chrono::NaiveDate;
+
+    #[derive(Debug, SageObject)]
+    struct Ecriture {
+        #[sage(name = "EC_Date")]
+        date: NaiveDate,
+        #[sage(name = "EC_Lettrage")]
+        lettrage: Option<String>,
+        #[sage(name = "cbMarq", read_only)]
+        marqueur: i32,
+        #[sage(skip)]
+        selectionnee: bool,
+    }
+
+    #[test]
+    fn test_derive_reads_and_writes_properties() {
+        let fake = FakeObject::new("IBOCompteG3")
+            .with_property("CG_Num", "411000")
+            .with_property("CG_Intitule", "Clients")
+            .with_property("CG_Sommeil", false);
+
+        let mut compte = CompteG::from_dispatch(fake.clone()).unwrap();
+        assert_eq!(compte.numero, "411000");
+        assert_eq!(CompteG::property_names(), &["CG_Num", "CG_Intitule", "CG_Sommeil"]);
+
+        compte.intitule = "Clients France".to_string();
+        compte.write_to(&fake).unwrap();
+        assert_eq!(fake.property("CG_Intitule").unwrap().to_string().unwrap(), "Clients France");
+    }
+
+    #[test]
+    fn test_derive_options_and_errors() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let fake = FakeObject::new("IBOEcriture3")
+            .with_property("EC_Date", SafeVariant::from_naive_date(date).unwrap())
+            .with_property("EC_Lettrage", SafeVariant::Null)
+            .with_property("cbMarq", 42);
+
+        let ecriture = Ecriture::read_from(&fake).unwrap();
+        assert_eq!((ecriture.date, ecriture.lettrage.as_deref(), ecriture.marqueur), (date, None, 42));
+        assert!(!ecriture.selectionnee);
+        assert!(fake.calls().iter().all(|c| c.kind == InvokeKind::PropertyGet));
+
+        // cbMarq est en lecture seule : seules les deux autres propriétés sont écrites
+        ecriture.write_to(&fake).unwrap();
+        let puts = fake.calls().iter().filter(|c| c.kind == InvokeKind::PropertyPut).count();
+        assert_eq!(puts, 2);
+
+        let error = Article::read_from(&fake).unwrap_err();
+        assert_eq!(error.contexts(), vec!["lecture de AR_Ref"]);
+    }
+}